@@ -1,6 +1,8 @@
 #![feature(test)]
 
 extern crate test;
+#[cfg(feature = "keysets")]
+use const_hashers::keysets;
 use core::hash::Hasher;
 use test::{Bencher, black_box};
 
@@ -32,6 +34,21 @@ tiny_bench!(tiny_lookup3, const_hashers::jenkins::lookup3);
 tiny_bench!(tiny_spooky, const_hashers::jenkins::spooky_hash::spooky);
 #[cfg(feature = "jenkins")]
 tiny_bench!(tiny_oaat, const_hashers::jenkins::oaat);
+#[cfg(feature = "pearson")]
+tiny_bench!(tiny_pearson8, const_hashers::pearson::pearson8);
+#[cfg(feature = "pearson")]
+tiny_bench!(tiny_pearson64, const_hashers::pearson::pearson64);
+#[cfg(feature = "tabulation")]
+tiny_bench!(tiny_tabulation, const_hashers::tabulation::tabulation);
+#[cfg(feature = "tabulation")]
+tiny_bench!(
+    tiny_twisted_tabulation,
+    const_hashers::tabulation::twisted_tabulation
+);
+#[cfg(feature = "universal")]
+tiny_bench!(tiny_poly61, const_hashers::universal::poly61);
+#[cfg(feature = "universal")]
+tiny_bench!(tiny_polymur, const_hashers::universal::polymur);
 #[cfg(feature = "null")]
 tiny_bench!(tiny_passthrough, const_hashers::passthrough);
 
@@ -188,6 +205,12 @@ w64_bench!(
 );
 #[cfg(feature = "oz")]
 w64_bench!(w64_10_bricolage, 10, const_hashers::pigeon::Bricolage);
+#[cfg(feature = "tabulation")]
+w64_bench!(
+    w64_10_twisted_tabulation,
+    10,
+    const_hashers::tabulation::TwistedTabulationHasher
+);
 
 #[cfg(feature = "builtin")]
 w64_bench!(w64_100_default, 100, const_hashers::builtin::DefaultHasher);
@@ -255,27 +278,17 @@ w64_bench!(
 #[cfg(feature = "oz")]
 w64_bench!(w64_1000_bricolage, 1000, const_hashers::pigeon::Bricolage);
 
-fn read_words() -> Vec<String> {
-    use std::fs::File;
-    use std::io::BufReader;
-    use std::io::prelude::*;
-
-    let file = File::open("./data/words.txt").expect("cannot open words.txt");
-    return BufReader::new(file)
-        .lines()
-        .map(|l| l.expect("bad read"))
-        .collect();
-}
-
+// The benches over the word list need the `keysets` feature.
 macro_rules! words_bench {
     ($name:ident, $count:expr, $($hasher:tt)*) => {
+        #[cfg(feature = "keysets")]
         #[bench]
         fn $name(b: &mut Bencher) {
-            let words = read_words();
+            let words = keysets::words();
             b.iter(|| {
                 let mut h = $($hasher)*::default();
                 for i in words.iter().take($count) {
-                    h.write(i.as_bytes());
+                    h.write(i);
                 }
                 black_box(h.finish())
             })
@@ -322,11 +335,10 @@ words_bench!(words1000_bricolage, 1000, const_hashers::pigeon::Bricolage);
 
 macro_rules! file_bench {
     ($name:ident, $($fcn:tt)*) => {
+        #[cfg(feature = "keysets")]
         #[bench]
         fn $name(b: &mut Bencher) {
-            use std::fs::read;
-            let file: Vec<u8> = read("./data/words.txt").expect("cannot read words.txt");
-            b.iter(|| black_box($($fcn)*(&file)))
+            b.iter(|| black_box($($fcn)*(keysets::WORDS)))
         }
     };
 }
@@ -353,3 +365,117 @@ file_bench!(file_fnv1a32, const_hashers::fnv::fnv1a32);
 file_bench!(file_spooky, const_hashers::jenkins::spooky_hash::spooky);
 #[cfg(feature = "oz")]
 file_bench!(file_bricolage, const_hashers::pigeon::bricolage);
+#[cfg(feature = "pearson")]
+file_bench!(file_pearson8, const_hashers::pearson::pearson8);
+#[cfg(feature = "pearson")]
+file_bench!(file_pearson64, const_hashers::pearson::pearson64);
+#[cfg(feature = "tabulation")]
+file_bench!(file_tabulation, const_hashers::tabulation::tabulation);
+#[cfg(feature = "tabulation")]
+file_bench!(
+    file_twisted_tabulation,
+    const_hashers::tabulation::twisted_tabulation
+);
+#[cfg(feature = "universal")]
+file_bench!(file_poly61, const_hashers::universal::poly61);
+#[cfg(feature = "universal")]
+file_bench!(file_polymur, const_hashers::universal::polymur);
+#[cfg(feature = "rolling")]
+file_bench!(
+    file_find_all,
+    (|f: &[u8]| const_hashers::rolling::find_all(f, b"zymurgy").count())
+);
+#[cfg(feature = "cdc")]
+file_bench!(
+    file_fastcdc,
+    (|f: &[u8]| const_hashers::cdc::FastCdc::default().chunks(f).count())
+);
+
+macro_rules! hashset_bench {
+    ($name:ident, $count:expr, $($hasher:tt)*) => {
+        #[cfg(feature = "keysets")]
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            use std::collections::HashSet;
+            use std::hash::BuildHasherDefault;
+
+            let words: Vec<&[u8]> = keysets::words().into_iter().take($count).collect();
+            b.iter(|| {
+                let mut set: HashSet<&[u8], BuildHasherDefault<$($hasher)*>> =
+                    HashSet::default();
+                set.extend(words.iter().copied());
+                black_box(words.iter().filter(|w| set.contains(*w)).count())
+            })
+        }
+    };
+}
+
+// std's HashMap takes its control bytes from the top 7 bits of the hash,
+// which the 32-bit Hashers, and LoseLose's small sums, leave zero; the
+// Finalized variants fill them.
+#[cfg(feature = "builtin")]
+hashset_bench!(
+    hashset10000_default,
+    10000,
+    const_hashers::builtin::DefaultHasher
+);
+#[cfg(feature = "oz")]
+hashset_bench!(hashset10000_djb2, 10000, const_hashers::oz::DJB2Hasher);
+#[cfg(all(feature = "oz", feature = "mixers"))]
+hashset_bench!(
+    hashset10000_djb2_finalized,
+    10000,
+    const_hashers::oz::DJB2Finalized
+);
+#[cfg(feature = "oz")]
+hashset_bench!(hashset10000_sdbm, 10000, const_hashers::oz::SDBMHasher);
+#[cfg(all(feature = "oz", feature = "mixers"))]
+hashset_bench!(
+    hashset10000_sdbm_finalized,
+    10000,
+    const_hashers::oz::SDBMFinalized
+);
+#[cfg(feature = "oz")]
+hashset_bench!(
+    hashset10000_loselose,
+    10000,
+    const_hashers::oz::LoseLoseHasher
+);
+#[cfg(all(feature = "oz", feature = "mixers"))]
+hashset_bench!(
+    hashset10000_loselose_finalized,
+    10000,
+    const_hashers::oz::LoseLoseFinalized
+);
+#[cfg(feature = "jenkins")]
+hashset_bench!(hashset10000_oaat, 10000, const_hashers::jenkins::OAATHasher);
+#[cfg(all(feature = "jenkins", feature = "mixers"))]
+hashset_bench!(
+    hashset10000_oaat_finalized,
+    10000,
+    const_hashers::jenkins::OAATFinalized
+);
+#[cfg(feature = "fnv")]
+hashset_bench!(
+    hashset10000_fnv1a32,
+    10000,
+    const_hashers::fnv::FNV1aHasher32
+);
+#[cfg(all(feature = "fnv", feature = "mixers"))]
+hashset_bench!(
+    hashset10000_fnv1a32_finalized,
+    10000,
+    const_hashers::fnv::FNV1aFinalized32
+);
+#[cfg(feature = "fnv")]
+hashset_bench!(
+    hashset10000_fnv1a64,
+    10000,
+    const_hashers::fnv::FNV1aHasher64
+);
+#[cfg(feature = "jenkins")]
+hashset_bench!(
+    hashset10000_spooky,
+    10000,
+    const_hashers::jenkins::spooky_hash::SpookyHasher
+);
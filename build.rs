@@ -0,0 +1,47 @@
+//! Generates `BUILD_SEED`, a random seed fixed for the life of one build.
+//!
+//! Set `CONST_HASHERS_BUILD_SEED` to a decimal or `0x`-prefixed hexadecimal
+//! u64 to choose the seed, for reproducible builds.
+
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::time::SystemTime;
+
+const SEED_VAR: &str = "CONST_HASHERS_BUILD_SEED";
+
+fn parse_seed(s: &str) -> Option<u64> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn random_seed() -> u64 {
+    // RandomState is keyed from the operating system's randomness.
+    let mut h = RandomState::new().build_hasher();
+    if let Ok(t) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        h.write_u128(t.as_nanos());
+    }
+    h.write_u32(std::process::id());
+    h.finish()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={}", SEED_VAR);
+
+    let seed = match env::var(SEED_VAR) {
+        Ok(s) => {
+            parse_seed(&s).unwrap_or_else(|| panic!("{} must be a u64, not {:?}", SEED_VAR, s))
+        }
+        Err(_) => random_seed(),
+    };
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+    let code = format!("pub const BUILD_SEED: u64 = {:#018x};\n", seed);
+    fs::write(Path::new(&out_dir).join("build_seed.rs"), code).expect("cannot write build_seed.rs");
+}
@@ -0,0 +1,38 @@
+#!/bin/sh
+# Downloads the upstream sources refgen.cpp is built against into
+# upstream/. burtleburtle.net has no version control, so its files come
+# from the Internet Archive's copy as of ARCHIVED; FNV comes from its
+# versioned release, and Polymur from its repository at POLYMUR_REV.
+#
+# The first run records the files' hashes in upstream/SHA256SUMS, which
+# should be committed; later runs check the downloads against it.
+
+set -eu
+
+ARCHIVED=20240101000000
+BURTLE="https://web.archive.org/web/${ARCHIVED}id_/http://burtleburtle.net/bob/c"
+FNV=fnv-5.0.3
+# The Polymur repository has no releases; SHA256SUMS pins what was fetched.
+POLYMUR_REV=master
+FILES="lookup3.c SpookyV2.h SpookyV2.cpp fnv.h hash_32a.c hash_64a.c have_ulong64.c polymur-hash.h"
+
+cd "$(dirname "$0")"
+mkdir -p upstream
+cd upstream
+
+for f in lookup3.c SpookyV2.h SpookyV2.cpp; do
+    curl -fsSL -o "$f" "$BURTLE/$f"
+done
+curl -fsSL "http://www.isthe.com/chongo/src/fnv/$FNV.tar.gz" |
+    tar -xzf - --strip-components=1 --wildcards \
+        '*/fnv.h' '*/hash_32a.c' '*/hash_64a.c' '*/have_ulong64.c'
+curl -fsSL -o polymur-hash.h \
+    "https://raw.githubusercontent.com/orlp/polymur-hash/$POLYMUR_REV/polymur-hash.h"
+
+if [ -f SHA256SUMS ]; then
+    sha256sum -c SHA256SUMS
+else
+    # shellcheck disable=SC2086
+    sha256sum $FILES > SHA256SUMS
+    echo "recorded upstream/SHA256SUMS; review and commit it"
+fi
@@ -0,0 +1,383 @@
+/*
+ * Generates the reference vectors in this directory.
+ *
+ *     make
+ *
+ * fetch.sh downloads the upstream sources into upstream/ and checks them
+ * against upstream/SHA256SUMS; the Makefile builds them unmodified and
+ * links this driver against them:
+ *
+ * - hashlittle2 from http://burtleburtle.net/bob/c/lookup3.c.
+ * - SpookyHash V2 from http://burtleburtle.net/bob/c/SpookyV2.cpp and
+ *   SpookyV2.h.
+ * - FNV-1a from hash_32a.c and hash_64a.c in the FNV reference release,
+ *   http://www.isthe.com/chongo/tech/comp/fnv/.
+ * - Polymur-hash 2.0 from polymur-hash.h in
+ *   https://github.com/orlp/polymur-hash.
+ *
+ * The other algorithms have no upstream source file, only code on a web
+ * page, and are transcribed below:
+ *
+ * - djb2, sdbm and lose lose from http://www.cse.yorku.ca/~oz/hash.html.
+ *   The page's functions read NUL-terminated strings, which the key
+ *   below is not, and give djb2's XOR variant only in prose; they use
+ *   `unsigned long` state, where the Rust Hashers keep 32 bits (djb2,
+ *   sdbm) or 64 bits (lose lose), and the low bits of these functions do
+ *   not depend on the high ones, so the results agree.
+ * - one-at-a-time from http://www.burtleburtle.net/bob/hash/doobs.html.
+ * - bricolage from Steven Pigeon's "Hash functions" posts,
+ *   https://hbfs.wordpress.com/2015/11/17/and-a-good-one-hash-functions-part-vi/
+ *   and https://hbfs.wordpress.com/2011/11/08/mild-obfuscation/, reading
+ *   each byte as unsigned.
+ *
+ * The only changes from upstream are in how each function is called: the
+ * seed is passed the way the Rust Hasher's `with_seed` takes it.
+ *
+ * The input is KEY_LEN bytes of a fixed pattern, and every algorithm is
+ * run on each prefix of it, for each seed in SEEDS. It also writes
+ * verification.txt, the SMHasher verification value of each algorithm.
+ */
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "SpookyV2.h"
+#include "polymur-hash.h"
+
+extern "C" {
+#include "fnv.h"
+
+void hashlittle2(const void *key, size_t length, uint32_t *pc, uint32_t *pb);
+}
+
+#define KEY_LEN 1024
+
+static const uint64_t SEEDS[] = {0, 1, 0x0123456789abcdefULL};
+#define NUM_SEEDS (sizeof(SEEDS) / sizeof(SEEDS[0]))
+
+static uint8_t key[KEY_LEN];
+
+static void make_key(void)
+{
+    for (int i = 0; i < KEY_LEN; i++)
+        key[i] = (uint8_t)(i * 97 + 13);
+}
+
+static uint32_t load32(const uint8_t *p)
+{
+    return (uint32_t)p[0] | (uint32_t)p[1] << 8 | (uint32_t)p[2] << 16 | (uint32_t)p[3] << 24;
+}
+
+/* ================================ oz */
+
+static uint32_t djb2(const uint8_t *str, size_t len, uint64_t seed)
+{
+    uint32_t hash = 5381 ^ (uint32_t)seed;
+    for (size_t i = 0; i < len; i++)
+        hash = ((hash << 5) + hash) ^ str[i]; /* hash(i) = hash(i - 1) * 33 ^ str[i] */
+    return hash;
+}
+
+static uint32_t sdbm(const uint8_t *str, size_t len, uint64_t seed)
+{
+    uint32_t hash = (uint32_t)seed;
+    for (size_t i = 0; i < len; i++)
+        hash = str[i] + (hash << 6) + (hash << 16) - hash;
+    return hash;
+}
+
+static uint64_t loselose(const uint8_t *str, size_t len, uint64_t seed)
+{
+    uint64_t hash = seed;
+    for (size_t i = 0; i < len; i++)
+        hash += str[i];
+    return hash;
+}
+
+/* ================================ one-at-a-time */
+
+static uint32_t one_at_a_time(const uint8_t *key, size_t len, uint64_t seed)
+{
+    uint32_t hash = (uint32_t)seed;
+    for (size_t i = 0; i < len; ++i) {
+        hash += key[i];
+        hash += (hash << 10);
+        hash ^= (hash >> 6);
+    }
+    hash += (hash << 3);
+    hash ^= (hash >> 11);
+    hash += (hash << 15);
+    return hash;
+}
+
+/* ================================ bricolage */
+
+static uint64_t cut_deck(uint64_t x)
+{
+    return (x << 32) | (x >> 32);
+}
+
+static uint32_t perfect_shuffle32(uint32_t x)
+{
+    x = (x & 0xff0000ffu) | ((x & 0x00ff0000u) >> 8) | ((x & 0x0000ff00u) << 8);
+    x = (x & 0xf00ff00fu) | ((x & 0x0f000f00u) >> 4) | ((x & 0x00f000f0u) << 4);
+    x = (x & 0xc3c3c3c3u) | ((x & 0x30303030u) >> 2) | ((x & 0x0c0c0c0cu) << 2);
+    x = (x & 0x99999999u) | ((x & 0x44444444u) >> 1) | ((x & 0x22222222u) << 1);
+    return x;
+}
+
+static uint64_t perfect_shuffle64(uint64_t x)
+{
+    x = cut_deck(x);
+    uint64_t xh = perfect_shuffle32((uint32_t)(x >> 32));
+    uint64_t xl = perfect_shuffle32((uint32_t)x);
+    return (xh << 32) | xl;
+}
+
+static uint64_t bricolage(const uint8_t *p, size_t len, uint64_t seed)
+{
+    uint64_t h = seed;
+    for (size_t i = 0; i < len; i++)
+        h = cut_deck(h + perfect_shuffle64(p[i] * 173773926194192273ULL));
+    return h;
+}
+
+/* ================================ output */
+
+/* The hash of `len` bytes, as returned by the Rust Hasher's finish. */
+typedef void (*hash_fn)(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2]);
+
+static void h_djb2(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = djb2(p, len, seed);
+}
+
+static void h_sdbm(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = sdbm(p, len, seed);
+}
+
+static void h_loselose(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = loselose(p, len, seed);
+}
+
+static void h_oaat(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = one_at_a_time(p, len, seed);
+}
+
+/* `Lookup3Hasher::with_seed(seed)` is `hashlittle2` with `pc` the low
+ * half of the seed and `pb` the high half; the hash is `pc | pb << 32`. */
+static void h_lookup3(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    uint32_t pc = (uint32_t)seed, pb = (uint32_t)(seed >> 32);
+    hashlittle2(p, len, &pc, &pb);
+    out[0] = (uint64_t)pc | (uint64_t)pb << 32;
+}
+
+/* `SpookyHasher::with_seed(seed)` is `Hash128` with both seeds `seed`. */
+static void h_spooky(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = seed;
+    out[1] = seed;
+    SpookyHash::Hash128(p, len, &out[0], &out[1]);
+}
+
+static void h_fnv1a32(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = fnv_32a_buf(const_cast<uint8_t *>(p), len, FNV1_32A_INIT ^ (uint32_t)seed);
+}
+
+static void h_fnv1a64(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = fnv_64a_buf(const_cast<uint8_t *>(p), len, FNV1A_64_INIT ^ seed);
+}
+
+static void h_bricolage(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    out[0] = bricolage(p, len, seed);
+}
+
+/* `Polymur::from_seed(seed)` is `polymur_init_params_from_seed`. The
+ * tweak is the one upstream's own tests use. */
+#define POLYMUR_TWEAK 0xabcdef0123456789ULL
+
+static void h_polymur(const uint8_t *p, size_t len, uint64_t seed, uint64_t out[2])
+{
+    PolymurHashParams params;
+    polymur_init_params_from_seed(&params, seed);
+    out[0] = polymur_hash(p, len, &params, POLYMUR_TWEAK);
+}
+
+struct algorithm {
+    const char *name;
+    hash_fn fn;
+    int words; /* 64-bit words of output: 2 for 128-bit hashes */
+};
+
+static const struct algorithm ALGORITHMS[] = {
+    {"djb2", h_djb2, 1},         {"sdbm", h_sdbm, 1},       {"loselose", h_loselose, 1},
+    {"oaat", h_oaat, 1},         {"lookup3", h_lookup3, 1}, {"spooky", h_spooky, 2},
+    {"fnv1a32", h_fnv1a32, 1},   {"fnv1a64", h_fnv1a64, 1}, {"bricolage", h_bricolage, 1},
+    {"polymur", h_polymur, 1},
+};
+#define NUM_ALGORITHMS (sizeof(ALGORITHMS) / sizeof(ALGORITHMS[0]))
+
+static FILE *create(const char *name)
+{
+    FILE *f = fopen(name, "w");
+    if (!f) {
+        perror(name);
+        exit(1);
+    }
+    return f;
+}
+
+static void write_vectors(const struct algorithm *alg)
+{
+    char path[64];
+    snprintf(path, sizeof(path), "%s.txt", alg->name);
+    FILE *f = create(path);
+    fprintf(f, "# %s over key[i] = i * 97 + 13 (mod 256), generated by refgen.cpp\n", alg->name);
+    fprintf(f, "# seed length hash\n");
+    for (size_t s = 0; s < NUM_SEEDS; s++) {
+        for (size_t len = 0; len <= KEY_LEN; len++) {
+            uint64_t out[2];
+            alg->fn(key, len, SEEDS[s], out);
+            fprintf(f, "%016llx %zu %016llx", (unsigned long long)SEEDS[s], len,
+                    (unsigned long long)out[0]);
+            if (alg->words == 2)
+                fprintf(f, " %016llx", (unsigned long long)out[1]);
+            fprintf(f, "\n");
+        }
+    }
+    fclose(f);
+}
+
+/* ================================ SMHasher verification values */
+
+/* SMHasher's signature: hash `len` bytes with a 32-bit seed into `out`,
+ * which is `hashbytes` long. */
+typedef void (*smhasher_fn)(const void *key, int len, uint32_t seed, void *out);
+
+static void sm_djb2(const void *key, int len, uint32_t seed, void *out)
+{
+    uint32_t h = djb2(static_cast<const uint8_t *>(key), len, seed);
+    memcpy(out, &h, 4);
+}
+
+static void sm_sdbm(const void *key, int len, uint32_t seed, void *out)
+{
+    uint32_t h = sdbm(static_cast<const uint8_t *>(key), len, seed);
+    memcpy(out, &h, 4);
+}
+
+static void sm_oaat(const void *key, int len, uint32_t seed, void *out)
+{
+    uint32_t h = one_at_a_time(static_cast<const uint8_t *>(key), len, seed);
+    memcpy(out, &h, 4);
+}
+
+/* SMHasher's lookup3_test: hashlittle(key, len, seed). */
+static void sm_lookup3(const void *key, int len, uint32_t seed, void *out)
+{
+    uint32_t pc = seed, pb = 0;
+    hashlittle2(key, len, &pc, &pb);
+    memcpy(out, &pc, 4);
+}
+
+static void sm_spooky32(const void *key, int len, uint32_t seed, void *out)
+{
+    uint64_t h1 = seed, h2 = seed;
+    SpookyHash::Hash128(key, len, &h1, &h2);
+    uint32_t h = (uint32_t)h1;
+    memcpy(out, &h, 4);
+}
+
+static void sm_spooky64(const void *key, int len, uint32_t seed, void *out)
+{
+    uint64_t h1 = seed, h2 = seed;
+    SpookyHash::Hash128(key, len, &h1, &h2);
+    memcpy(out, &h1, 8);
+}
+
+static void sm_spooky128(const void *key, int len, uint32_t seed, void *out)
+{
+    uint64_t h[2] = {seed, seed};
+    SpookyHash::Hash128(key, len, &h[0], &h[1]);
+    memcpy(out, h, 16);
+}
+
+/* SMHasher's FNV: FNV-1a 32 with the seed xored into the offset basis. */
+static void sm_fnv1a32(const void *key, int len, uint32_t seed, void *out)
+{
+    uint32_t h = fnv_32a_buf(const_cast<void *>(key), len, FNV1_32A_INIT ^ seed);
+    memcpy(out, &h, 4);
+}
+
+static void sm_fnv1a64(const void *key, int len, uint32_t seed, void *out)
+{
+    uint64_t h = fnv_64a_buf(const_cast<void *>(key), len, FNV1A_64_INIT ^ seed);
+    memcpy(out, &h, 8);
+}
+
+static void sm_bricolage(const void *key, int len, uint32_t seed, void *out)
+{
+    uint64_t h = bricolage(static_cast<const uint8_t *>(key), len, seed);
+    memcpy(out, &h, 8);
+}
+
+/* SMHasher's VerificationTest: hash keys of the form {0, 1, ..., n-1}
+ * with seed 256 - n, for n from 0 to 255, then hash the concatenated
+ * results with seed 0. The first four bytes of that, little-endian, are
+ * the verification value. */
+static uint32_t verification(smhasher_fn hash, int hashbytes)
+{
+    uint8_t k[256];
+    uint8_t *hashes = static_cast<uint8_t *>(calloc(256, hashbytes));
+    uint8_t final[16];
+
+    for (int i = 0; i < 256; i++) {
+        k[i] = (uint8_t)i;
+        hash(k, i, 256 - i, &hashes[i * hashbytes]);
+    }
+    hash(hashes, hashbytes * 256, 0, final);
+    free(hashes);
+    return load32(final);
+}
+
+struct smhasher_algorithm {
+    const char *name;
+    smhasher_fn fn;
+    int hashbytes;
+};
+
+static const struct smhasher_algorithm SMHASHER_ALGORITHMS[] = {
+    {"djb2", sm_djb2, 4},         {"sdbm", sm_sdbm, 4},
+    {"oaat", sm_oaat, 4},         {"lookup3", sm_lookup3, 4},
+    {"spooky32", sm_spooky32, 4}, {"spooky64", sm_spooky64, 8},
+    {"spooky128", sm_spooky128, 16}, {"fnv1a32", sm_fnv1a32, 4},
+    {"fnv1a64", sm_fnv1a64, 8},   {"bricolage", sm_bricolage, 8},
+};
+#define NUM_SMHASHER_ALGORITHMS (sizeof(SMHASHER_ALGORITHMS) / sizeof(SMHASHER_ALGORITHMS[0]))
+
+int main(void)
+{
+    make_key();
+    for (size_t a = 0; a < NUM_ALGORITHMS; a++)
+        write_vectors(&ALGORITHMS[a]);
+
+    FILE *f = create("verification.txt");
+    fprintf(f, "# SMHasher verification values, generated by refgen.cpp\n");
+    fprintf(f, "# algorithm hash-bits value\n");
+    for (size_t a = 0; a < NUM_SMHASHER_ALGORITHMS; a++) {
+        const struct smhasher_algorithm *alg = &SMHASHER_ALGORITHMS[a];
+        fprintf(f, "%s %d %08x\n", alg->name, alg->hashbytes * 8,
+                verification(alg->fn, alg->hashbytes));
+    }
+    fclose(f);
+    return 0;
+}
@@ -12,7 +12,7 @@ pub mod combinations;
 fn get_letters(s: &str) -> Vec<u8> {
     let mut t: Vec<char> = s.chars().collect();
     t.sort();
-    return t.iter().map(|&ch| ch as u8).collect();
+    t.iter().map(|&ch| ch as u8).collect()
 }
 
 /// Split a line into a vector of space-separated words.
@@ -40,7 +40,7 @@ fn load_dictionary<H: Default + Hasher>() -> Dictionary<BuildHasherDefault<H>> {
         let key: Vec<u8> = words.remove(0).chars().map(|ch| ch as u8).collect();
         map.insert(key, words);
     }
-    return map;
+    map
 }
 
 /// Search all combinations of letters, returning a set of the matching words.
@@ -53,20 +53,15 @@ fn search<H: Default + Hasher, BH: BuildHasher>(
         let mut key: Vec<u8> = vec![0; i];
         // note the internal iterator
         combinations::each_combination(letters, i, |combo| {
-            for j in 0..combo.len() {
-                key[j] = combo[j];
-            }
-            match dictionary.get(&key) {
-                Some(val) => {
-                    for word in val.iter() {
-                        set.insert(word.clone());
-                    }
+            key[..combo.len()].copy_from_slice(combo);
+            if let Some(val) = dictionary.get(&key) {
+                for word in val.iter() {
+                    set.insert(word.clone());
                 }
-                None => {}
             }
         });
     }
-    return set;
+    set
 }
 
 /// Return the number of words found using a particular string.
@@ -0,0 +1,68 @@
+// The SMHasher-style test battery, for each Hasher. The optional
+// argument scales the number of random trials and keys; the default is
+// 1.0.
+
+use const_hashers::battery::{Battery, HashFn};
+
+fn do_battery(name: &str, battery: Battery<HashFn>) {
+    let outcomes = battery.run();
+    let failed = outcomes.iter().filter(|o| !o.passed).count();
+    println!("{}: {} of {} tests failed", name, failed, outcomes.len());
+    for outcome in outcomes {
+        println!("    {}", outcome);
+    }
+}
+
+fn main() {
+    let scale: f64 = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().expect("scale must be a number"))
+        .unwrap_or(1.0);
+
+    #[cfg(feature = "pigeon")]
+    do_battery(
+        "bricolage",
+        Battery::for_hasher::<const_hashers::pigeon::Bricolage>(64).with_scale(scale),
+    );
+    #[cfg(feature = "oz")]
+    do_battery(
+        "djb2",
+        Battery::for_hasher::<const_hashers::oz::DJB2Hasher>(32).with_scale(scale),
+    );
+    #[cfg(feature = "fnv")]
+    do_battery(
+        "fnv1a 32",
+        Battery::for_hasher::<const_hashers::fnv::FNV1aHasher32>(32).with_scale(scale),
+    );
+    #[cfg(feature = "fnv")]
+    do_battery(
+        "fnv1a 64",
+        Battery::for_hasher::<const_hashers::fnv::FNV1aHasher64>(64).with_scale(scale),
+    );
+    #[cfg(feature = "jenkins")]
+    do_battery(
+        "lookup3",
+        Battery::for_hasher::<const_hashers::jenkins::Lookup3Hasher>(64).with_scale(scale),
+    );
+    #[cfg(feature = "oz")]
+    do_battery(
+        "loselose",
+        Battery::for_hasher::<const_hashers::oz::LoseLoseHasher>(64).with_scale(scale),
+    );
+    #[cfg(feature = "jenkins")]
+    do_battery(
+        "OAAT",
+        Battery::for_hasher::<const_hashers::jenkins::OAATHasher>(32).with_scale(scale),
+    );
+    #[cfg(feature = "oz")]
+    do_battery(
+        "sdbm",
+        Battery::for_hasher::<const_hashers::oz::SDBMHasher>(32).with_scale(scale),
+    );
+    #[cfg(feature = "jenkins")]
+    do_battery(
+        "spooky",
+        Battery::for_hasher::<const_hashers::jenkins::spooky_hash::SpookyHasher>(64)
+            .with_scale(scale),
+    );
+}
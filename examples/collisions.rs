@@ -0,0 +1,61 @@
+// Collision counts against the birthday bound: for each Hasher and each
+// keyset, the observed and expected collisions among the low bits of the
+// hashes at a few of the widths from 16 to 64 bits, and the widths where
+// a random function would rarely have as many.
+
+use const_hashers::collisions::{CollisionReport, count};
+use const_hashers::keysets;
+
+const SHOWN: [u32; 5] = [24, 32, 40, 48, 64];
+
+fn do_count<K: AsRef<[u8]>>(name: &str, hash: fn(&[u8]) -> u64, keys: &[K]) {
+    let report: CollisionReport = count(hash, keys);
+    let excesses = report.excesses(0.01);
+    println!("{}", name);
+    for w in report.widths.iter().filter(|w| SHOWN.contains(&w.bits)) {
+        let flag = if excesses.contains(w) { "  EXCESS" } else { "" };
+        println!("    {}{}", w, flag);
+    }
+    if let (Some(first), Some(last)) = (excesses.first(), excesses.last()) {
+        println!(
+            "    FAIL: excess collisions at {} of {} widths, from {} to {} bits",
+            excesses.len(),
+            report.widths.len(),
+            first.bits,
+            last.bits
+        );
+    }
+}
+
+fn do_hashes<K: AsRef<[u8]>>(keys: &[K]) {
+    #[cfg(feature = "pigeon")]
+    do_count("bricolage", const_hashers::pigeon::bricolage, keys);
+    #[cfg(feature = "oz")]
+    do_count("djb2", const_hashers::oz::djb2, keys);
+    #[cfg(feature = "fnv")]
+    do_count("fnv1a 32", const_hashers::fnv::fnv1a32, keys);
+    #[cfg(feature = "fnv")]
+    do_count("fnv1a 64", const_hashers::fnv::fnv1a64, keys);
+    #[cfg(feature = "jenkins")]
+    do_count("lookup3", const_hashers::jenkins::lookup3, keys);
+    #[cfg(feature = "jenkins")]
+    do_count("OAAT", const_hashers::jenkins::oaat, keys);
+    #[cfg(feature = "oz")]
+    do_count("sdbm", const_hashers::oz::sdbm, keys);
+    #[cfg(feature = "jenkins")]
+    do_count("spooky", const_hashers::jenkins::spooky_hash::spooky, keys);
+}
+
+fn main() {
+    println!("Dictionary words");
+    do_hashes(&keysets::words());
+
+    println!("\nGenerated identifiers");
+    do_hashes(&keysets::identifiers(1_000_000, 7));
+
+    println!("\nUUIDs");
+    do_hashes(&keysets::uuids(1_000_000, 1));
+
+    println!("\nURLs");
+    do_hashes(&keysets::urls(1_000_000, 1));
+}
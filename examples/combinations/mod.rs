@@ -14,7 +14,7 @@
 /// # Arguments
 ///
 /// * `values` - A vector of values from which the combinations are
-/// chosen
+///   chosen
 ///
 /// * `r` - The length of the emitted combinations
 ///
@@ -25,10 +25,10 @@
 /// This function gleefully stolen from Python
 /// [`itertools.combinations`](http://docs.python.org/2/library/itertools.html#itertools.combinations).
 #[inline]
-pub fn each_combination<T, F>(values: &[T], r: usize, mut fun: F) -> ()
+pub fn each_combination<T, F>(values: &[T], r: usize, mut fun: F)
 where
     T: Clone,
-    F: FnMut(&[T]) -> (),
+    F: FnMut(&[T]),
 {
     let length = values.len();
     if r == 0 || r > length {
@@ -36,7 +36,7 @@ where
     }
     let max_indices0 = length - r;
     let mut indices: Vec<usize> = (0..r).collect();
-    let mut combination: Vec<T> = values[0..r].iter().cloned().collect();
+    let mut combination: Vec<T> = values[0..r].to_vec();
     loop {
         fun(&combination);
         // Increment the indices
@@ -74,17 +74,17 @@ where
 /// # Arguments
 ///
 /// * `values` - A vector of values from which the combinations are
-/// chosen
+///   chosen
 ///
 /// * `r` - The length of the emitted combinations
 ///
 /// * `fun` - The function to iterate over the combinations
 #[inline]
-pub fn each_combination_ref<'v, T, F>(values: &'v [T], r: usize, fun: F) -> ()
+pub fn each_combination_ref<'v, T, F>(values: &'v [T], r: usize, fun: F)
 where
-    F: FnMut(&[&'v T]) -> (),
+    F: FnMut(&[&'v T]),
 {
-    let v: Vec<&T> = values.iter().map(|elt| elt).collect();
+    let v: Vec<&T> = values.iter().collect();
     each_combination(&v, r, fun);
 }
 
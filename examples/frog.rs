@@ -0,0 +1,38 @@
+// Bob Jenkins' frog.c test, scaled down: the first collision in each
+// 32-bit window of the hashes of 16-byte keys with at most 3 bits set.
+// A random function collides after about 82,000 keys.
+
+use const_hashers::frog::frog;
+
+fn do_frog(name: &str, hash: fn(&[u8]) -> u64, bits: u32) {
+    println!("{}", name);
+    // a 32-bit hash has only one 32-bit window
+    for c in frog(hash, 16, 3, 350_000, 32)
+        .into_iter()
+        .filter(|c| c.offset + c.bits <= bits)
+    {
+        let verdict = if c.passes(0.001) { "" } else { "  FAIL" };
+        println!("    {}{}", c, verdict);
+    }
+}
+
+fn main() {
+    #[cfg(feature = "pigeon")]
+    do_frog("bricolage", const_hashers::pigeon::bricolage, 64);
+    #[cfg(feature = "oz")]
+    do_frog("djb2", const_hashers::oz::djb2, 32);
+    #[cfg(feature = "fnv")]
+    do_frog("fnv1a 32", const_hashers::fnv::fnv1a32, 32);
+    #[cfg(feature = "fnv")]
+    do_frog("fnv1a 64", const_hashers::fnv::fnv1a64, 64);
+    #[cfg(feature = "jenkins")]
+    do_frog("lookup3", const_hashers::jenkins::lookup3, 64);
+    #[cfg(feature = "oz")]
+    do_frog("loselose", const_hashers::oz::loselose, 64);
+    #[cfg(feature = "jenkins")]
+    do_frog("OAAT", const_hashers::jenkins::oaat, 32);
+    #[cfg(feature = "oz")]
+    do_frog("sdbm", const_hashers::oz::sdbm, 32);
+    #[cfg(feature = "jenkins")]
+    do_frog("spooky", const_hashers::jenkins::spooky_hash::spooky, 64);
+}
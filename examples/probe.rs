@@ -0,0 +1,62 @@
+// Simulated hash tables: probe lengths for linear probing, Robin Hood and
+// SwissTable layouts at several load factors, for each Hasher, and
+// warnings where they are much worse than a random function's.
+
+use const_hashers::keysets;
+use const_hashers::probe::Simulation;
+
+const LOAD_FACTORS: [f64; 3] = [0.5, 0.75, 0.875];
+
+fn do_simulation<K: AsRef<[u8]>>(name: &str, hash: fn(&[u8]) -> u64, samples: &[K]) {
+    let simulation = Simulation::of(hash, samples, &LOAD_FACTORS);
+    println!("{}", name);
+    for (result, random) in simulation.results.iter().zip(&simulation.random) {
+        println!("    {}  (random {:.3})", result, random.mean());
+    }
+    for warning in simulation.warnings() {
+        println!("    WARNING: {}", warning);
+    }
+}
+
+fn do_hashes<K: AsRef<[u8]>>(samples: &[K]) {
+    #[cfg(feature = "pigeon")]
+    do_simulation("bricolage", const_hashers::pigeon::bricolage, samples);
+    #[cfg(feature = "oz")]
+    do_simulation("djb2", const_hashers::oz::djb2, samples);
+    #[cfg(feature = "fnv")]
+    do_simulation("fnv1a 32", const_hashers::fnv::fnv1a32, samples);
+    #[cfg(feature = "fnv")]
+    do_simulation("fnv1a 64", const_hashers::fnv::fnv1a64, samples);
+    #[cfg(feature = "jenkins")]
+    do_simulation("lookup3", const_hashers::jenkins::lookup3, samples);
+    #[cfg(feature = "oz")]
+    do_simulation("loselose", const_hashers::oz::loselose, samples);
+    #[cfg(feature = "jenkins")]
+    do_simulation("OAAT", const_hashers::jenkins::oaat, samples);
+    #[cfg(feature = "oz")]
+    do_simulation("sdbm", const_hashers::oz::sdbm, samples);
+    #[cfg(feature = "jenkins")]
+    do_simulation(
+        "spooky",
+        const_hashers::jenkins::spooky_hash::spooky,
+        samples,
+    );
+}
+
+fn main() {
+    println!("Uniform distribution");
+    let s1 = keysets::uniform(100_000, 6, 1);
+    do_hashes(&s1);
+
+    println!("\nAlphanumeric distribution");
+    let s2 = keysets::alphanumeric(100_000, 6, 2);
+    do_hashes(&s2);
+
+    println!("\nGenerated identifiers");
+    let s3 = keysets::identifiers(100_000, 6);
+    do_hashes(&s3);
+
+    println!("\nDictionary words");
+    let s4 = keysets::words();
+    do_hashes(&s4);
+}
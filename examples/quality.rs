@@ -0,0 +1,60 @@
+// Statistical tests of the Hashers: see the quality module.
+
+use const_hashers::keysets;
+use const_hashers::quality::QualityReport;
+
+fn do_print<K: AsRef<[u8]>>(name: &str, hash: fn(&[u8]) -> u64, bits: u32, samples: &[K]) {
+    let report = QualityReport::new(hash, samples, bits);
+    let verdict = if report.passes(0.01) { "" } else { "FAIL" };
+    println!("{: <9}:  {}  {}", name, report, verdict);
+}
+
+fn do_hashes<K: AsRef<[u8]>>(samples: &[K]) {
+    #[cfg(feature = "pigeon")]
+    do_print("bricolage", const_hashers::pigeon::bricolage, 64, samples);
+    #[cfg(feature = "builtin")]
+    do_print("default", const_hashers::builtin::default, 64, samples);
+    #[cfg(feature = "oz")]
+    do_print("djb2", const_hashers::oz::djb2, 32, samples);
+    #[cfg(feature = "fnv")]
+    do_print("fnv1a 32", const_hashers::fnv::fnv1a32, 32, samples);
+    #[cfg(feature = "fnv")]
+    do_print("fnv1a 64", const_hashers::fnv::fnv1a64, 64, samples);
+    #[cfg(feature = "jenkins")]
+    do_print("lookup3", const_hashers::jenkins::lookup3, 64, samples);
+    #[cfg(feature = "oz")]
+    do_print("loselose", const_hashers::oz::loselose, 64, samples);
+    #[cfg(feature = "null")]
+    do_print("null", const_hashers::null::null, 64, samples);
+    #[cfg(feature = "jenkins")]
+    do_print("OAAT", const_hashers::jenkins::oaat, 32, samples);
+    #[cfg(feature = "null")]
+    do_print("Pass", const_hashers::null::passthrough, 64, samples);
+    #[cfg(feature = "oz")]
+    do_print("sdbm", const_hashers::oz::sdbm, 32, samples);
+    #[cfg(feature = "jenkins")]
+    do_print(
+        "spooky",
+        const_hashers::jenkins::spooky_hash::spooky,
+        64,
+        samples,
+    );
+}
+
+fn main() {
+    println!("Uniform distribution");
+    let s1 = keysets::uniform(1000, 6, 1);
+    do_hashes(&s1);
+
+    println!("\nAlphanumeric distribution");
+    let s2 = keysets::alphanumeric(1000, 6, 2);
+    do_hashes(&s2);
+
+    println!("\nGenerated identifiers");
+    let s3 = keysets::identifiers(1000, 6);
+    do_hashes(&s3);
+
+    println!("\nDictionary words");
+    let s4 = keysets::words();
+    do_hashes(&s4);
+}
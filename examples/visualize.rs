@@ -0,0 +1,68 @@
+// Pictures of the Hashers: for each, an avalanche matrix of 8-byte keys
+// and a bitmap of the hashes of sequential keys, as PPM, PGM and CSV
+// files in the directory given as the argument (default
+// target/visual).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use const_hashers::battery::AvalancheMatrix;
+use const_hashers::visual::{
+    avalanche_csv, avalanche_image, bitmap_csv, bitmap_image, sequential_hashes,
+};
+
+fn do_pictures(dir: &Path, name: &str, hash: fn(&[u8]) -> u64, bits: u32) {
+    let write = |file: String, contents: &[u8]| {
+        let path = dir.join(file);
+        fs::write(&path, contents)
+            .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+    };
+    let matrix = AvalancheMatrix::new(hash, 8, bits, 10_000, 0);
+    let image = avalanche_image(&matrix).scaled(8);
+    write(format!("{}-avalanche.ppm", name), &image.ppm());
+    write(
+        format!("{}-avalanche.csv", name),
+        avalanche_csv(&matrix).as_bytes(),
+    );
+
+    let hashes = sequential_hashes(hash, 256);
+    let image = bitmap_image(&hashes, bits).scaled(4);
+    write(format!("{}-sequential.pgm", name), &image.pgm());
+    write(
+        format!("{}-sequential.csv", name),
+        bitmap_csv(&hashes, bits).as_bytes(),
+    );
+    println!("{}: {}/{}-*", name, dir.display(), name);
+}
+
+fn main() {
+    let dir: PathBuf = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "target/visual".to_string())
+        .into();
+    fs::create_dir_all(&dir).expect("cannot create the output directory");
+
+    #[cfg(feature = "pigeon")]
+    do_pictures(&dir, "bricolage", const_hashers::pigeon::bricolage, 64);
+    #[cfg(feature = "oz")]
+    do_pictures(&dir, "djb2", const_hashers::oz::djb2, 32);
+    #[cfg(feature = "fnv")]
+    do_pictures(&dir, "fnv1a32", const_hashers::fnv::fnv1a32, 32);
+    #[cfg(feature = "fnv")]
+    do_pictures(&dir, "fnv1a64", const_hashers::fnv::fnv1a64, 64);
+    #[cfg(feature = "jenkins")]
+    do_pictures(&dir, "lookup3", const_hashers::jenkins::lookup3, 64);
+    #[cfg(feature = "oz")]
+    do_pictures(&dir, "loselose", const_hashers::oz::loselose, 64);
+    #[cfg(feature = "jenkins")]
+    do_pictures(&dir, "oaat", const_hashers::jenkins::oaat, 32);
+    #[cfg(feature = "oz")]
+    do_pictures(&dir, "sdbm", const_hashers::oz::sdbm, 32);
+    #[cfg(feature = "jenkins")]
+    do_pictures(
+        &dir,
+        "spooky",
+        const_hashers::jenkins::spooky_hash::spooky,
+        64,
+    );
+}
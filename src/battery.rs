@@ -0,0 +1,712 @@
+//! A battery of hash function tests in the style of Austin Appleby's
+//! SMHasher (https://github.com/aappleby/smhasher).
+//!
+//! - [`Battery::avalanche`]: the strict avalanche criterion. Flipping any
+//!   one input bit flips each output bit with probability 1/2.
+//! - [`Battery::bit_independence`]: the bit independence criterion.
+//!   Flipping any one input bit flips any two output bits independently.
+//! - [`Battery::differential`]: no sparse difference between keys gives
+//!   equal hashes more often than chance.
+//! - Keyset tests, which hash a structured set of keys and check the
+//!   hashes for collisions and for bias in every window of bits:
+//!   [`cyclic`](Battery::cyclic), [`sparse`](Battery::sparse),
+//!   [`two_bytes`](Battery::two_bytes),
+//!   [`permutation`](Battery::permutation),
+//!   [`combination`](Battery::combination), [`window`](Battery::window),
+//!   [`text`](Battery::text), [`zeroes`](Battery::zeroes) and
+//!   [`seed`](Battery::seed), which hashes one key with many seeds.
+//!
+//! Every test gives an [`Outcome`]: the measured bias, 0 for a random
+//! function, and a p-value, the probability that a random function would
+//! be at least that biased, corrected for the number of cells, windows or
+//! pairs of bits looked at. The test passes if the p-value is at least
+//! the battery's significance level. Keys are generated deterministically,
+//! so a Hasher either always passes a test or always fails it.
+//!
+//! The tests are much smaller than SMHasher's, to run in seconds rather
+//! than hours. [`Battery::with_scale`] scales the number of random trials
+//! and keys.
+
+use core::fmt;
+
+use crate::SeedableHasher;
+use crate::mixers::SplitMix64;
+use crate::quality::{
+    self, bucket_bits, expected_collisions, normal_upper_tail, poisson_upper_tail,
+};
+
+/// A hash function of a key and a seed.
+pub type HashFn = fn(&[u8], u64) -> u64;
+
+/// Hash `key` with `H::with_seed(seed)`, in a single `write`.
+pub fn hash_with<H: SeedableHasher>(key: &[u8], seed: u64) -> u64 {
+    let mut h = H::with_seed(seed);
+    h.write(key);
+    h.finish()
+}
+
+/// The result of one test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    /// The test and the keys it used.
+    pub test: String,
+    /// The measured bias, from 0 for a random function to 1 for the worst
+    /// possible: for the avalanche tests, the largest |2p - 1| for a bit
+    /// flip probability p; for collisions, the excess of collisions over
+    /// those expected, as a fraction of the keys; and for the
+    /// distribution of hashes, the effect size sqrt((χ² - dof) / (n
+    /// dof)) of the worst window.
+    pub bias: f64,
+    /// The probability that a random function would be at least this
+    /// biased.
+    pub p_value: f64,
+    /// What was measured, e.g. the number of collisions and the number
+    /// expected.
+    pub detail: String,
+    /// Whether the p-value is at least the battery's significance level.
+    pub passed: bool,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<44} {:4}  bias {:.4}  p = {:.4}  ({})",
+            self.test,
+            if self.passed { "pass" } else { "FAIL" },
+            self.bias,
+            self.p_value,
+            self.detail
+        )
+    }
+}
+
+/// The tests, for one hash function.
+#[derive(Copy, Clone, Debug)]
+pub struct Battery<F> {
+    hash: F,
+    bits: u32,
+    scale: f64,
+    alpha: f64,
+}
+
+impl Battery<HashFn> {
+    /// Test a Hasher whose results are `bits` wide, as [`hash_with`].
+    pub fn for_hasher<H: SeedableHasher>(bits: u32) -> Battery<HashFn> {
+        Battery::new(hash_with::<H>, bits)
+    }
+}
+
+impl<F: Fn(&[u8], u64) -> u64> Battery<F> {
+    /// Test `hash`, whose results are `bits` wide; higher bits are
+    /// ignored. The battery starts at scale 1.0, where each test takes
+    /// well under a second for a fast hash in a release build, and
+    /// significance level 0.001.
+    pub fn new(hash: F, bits: u32) -> Battery<F> {
+        assert!((8..=64).contains(&bits), "bits must be in 8..=64");
+        Battery {
+            hash,
+            bits,
+            scale: 1.0,
+            alpha: 0.001,
+        }
+    }
+
+    /// Multiply the number of random trials and random keys by `scale`.
+    /// The exhaustive keysets are the same at every scale.
+    pub fn with_scale(mut self, scale: f64) -> Battery<F> {
+        assert!(scale > 0.0);
+        self.scale = scale;
+        self
+    }
+
+    /// Pass tests whose p-value is at least `alpha`.
+    pub fn with_alpha(mut self, alpha: f64) -> Battery<F> {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Run every test.
+    pub fn run(&self) -> Vec<Outcome> {
+        let mut outcomes = Vec::new();
+        outcomes.extend(self.avalanche());
+        outcomes.extend(self.bit_independence());
+        outcomes.extend(self.differential());
+        outcomes.extend(self.cyclic());
+        outcomes.extend(self.sparse());
+        outcomes.extend(self.two_bytes());
+        outcomes.extend(self.permutation());
+        outcomes.extend(self.combination());
+        outcomes.extend(self.window());
+        outcomes.extend(self.text());
+        outcomes.extend(self.zeroes());
+        outcomes.extend(self.seed());
+        outcomes
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+
+    fn hash(&self, key: &[u8], seed: u64) -> u64 {
+        (self.hash)(key, seed) & self.mask()
+    }
+
+    /// `n` scaled, but at least `min`.
+    fn scaled(&self, n: usize, min: usize) -> usize {
+        ((n as f64 * self.scale) as usize).max(min)
+    }
+
+    fn outcome(&self, test: String, bias: f64, p_value: f64, detail: String) -> Outcome {
+        Outcome {
+            test,
+            bias,
+            p_value,
+            detail,
+            passed: p_value >= self.alpha,
+        }
+    }
+
+    /// The outcome of `trials` trials of `cells` coin flips, of which
+    /// the most biased came up heads `heads` times.
+    fn worst_cell(&self, test: String, heads: &[u64], trials: usize) -> Outcome {
+        let (cell, bias) = heads
+            .iter()
+            .map(|&c| (2.0 * c as f64 / trials as f64 - 1.0).abs())
+            .enumerate()
+            .fold(
+                (0, 0.0),
+                |worst, (i, b)| if b > worst.1 { (i, b) } else { worst },
+            );
+        let z = bias * (trials as f64).sqrt();
+        let p_value = (2.0 * heads.len() as f64 * normal_upper_tail(z)).min(1.0);
+        let detail = format!(
+            "worst of {} cells is #{}, {} trials",
+            heads.len(),
+            cell,
+            trials
+        );
+        self.outcome(test, bias, p_value, detail)
+    }
+
+    // ====================================
+    // Avalanche
+
+    /// The strict avalanche criterion, for random keys of 4, 8, 16 and 32
+    /// bytes: the probability that each output bit flips when each input
+    /// bit is flipped.
+    pub fn avalanche(&self) -> Vec<Outcome> {
+        let trials = self.scaled(10_000, 100);
+        [4, 8, 16, 32]
+            .iter()
+            .map(|&len| {
+                let hash = |key: &[u8]| self.hash(key, 0);
+                let matrix = AvalancheMatrix::new(hash, len, self.bits, trials, len as u64);
+                let test = format!("avalanche, {}-byte keys", len);
+                self.worst_cell(test, &matrix.flips, trials)
+            })
+            .collect()
+    }
+
+    /// The bit independence criterion, for random 8-byte keys: when each
+    /// input bit is flipped, whether each pair of output bits flip
+    /// together or apart.
+    pub fn bit_independence(&self) -> Vec<Outcome> {
+        let trials = self.scaled(2_000, 40);
+        let bits = self.bits as usize;
+        let pairs = bits * (bits - 1) / 2;
+        let mut rng = SplitMix64::new(0xb1c);
+        let mut heads = vec![0u64; 64 * pairs];
+        for _ in 0..trials {
+            let mut key = random_key(&mut rng, 8);
+            let h0 = self.hash(&key, 0);
+            for i in 0..64 {
+                flip(&mut key, i);
+                let d = h0 ^ self.hash(&key, 0);
+                flip(&mut key, i);
+                let mut cell = i * pairs;
+                for j in 0..bits {
+                    for k in j + 1..bits {
+                        heads[cell] += ((d >> j) ^ (d >> k)) & 1;
+                        cell += 1;
+                    }
+                }
+            }
+        }
+        vec![self.worst_cell("bit independence, 8-byte keys".to_string(), &heads, trials)]
+    }
+
+    /// Differential collisions: for random 8-byte keys k and every
+    /// difference d of up to three bits, how often the hashes of k and
+    /// k ^ d are equal.
+    pub fn differential(&self) -> Vec<Outcome> {
+        let trials = self.scaled(100, 2);
+        let diffs = sparse_words(64, 3);
+        let mut rng = SplitMix64::new(0xd1ff);
+        let mut collisions = 0u64;
+        for _ in 0..trials {
+            let k = rng.next();
+            let h0 = self.hash(&k.to_le_bytes(), 0);
+            for &d in &diffs[1..] {
+                if self.hash(&(k ^ d).to_le_bytes(), 0) == h0 {
+                    collisions += 1;
+                }
+            }
+        }
+        let pairs = trials * (diffs.len() - 1);
+        let expected = pairs as f64 / 2f64.powi(self.bits as i32);
+        vec![self.outcome(
+            "differential, 8-byte keys, up to 3 bits".to_string(),
+            (collisions as f64 - expected).max(0.0) / pairs as f64,
+            poisson_upper_tail(collisions, expected),
+            format!("{} collisions, {:.4} expected", collisions, expected),
+        )]
+    }
+
+    // ====================================
+    // Keysets
+
+    /// Test the hashes of a keyset, for collisions and for bias in any
+    /// window of bits.
+    fn keyset(&self, test: String, mut hashes: Vec<u64>) -> Vec<Outcome> {
+        let n = hashes.len();
+        let window = bucket_bits(n, self.bits);
+        let mut worst = (0, 0.0, 1.0);
+        for offset in 0..self.bits {
+            let rotated: Vec<u64> = hashes.iter().map(|&h| self.rotate(h, offset)).collect();
+            let result = quality::chi2(&rotated, window);
+            if result.p_value <= worst.2 {
+                let dof = ((1u64 << window) - 1) as f64;
+                let bias = ((result.statistic - dof).max(0.0) / (n as f64 * dof)).sqrt();
+                worst = (offset, bias, result.p_value);
+            }
+        }
+        let distribution = self.outcome(
+            format!("{}: distribution", test),
+            worst.1,
+            (worst.2 * self.bits as f64).min(1.0),
+            format!("worst {}-bit window at bit {}, {} keys", window, worst.0, n),
+        );
+
+        hashes.sort_unstable();
+        hashes.dedup();
+        let collisions = (n - hashes.len()) as u64;
+        let expected = expected_collisions(n, self.bits);
+        let collisions = self.outcome(
+            format!("{}: collisions", test),
+            (collisions as f64 - expected).max(0.0) / n as f64,
+            poisson_upper_tail(collisions, expected),
+            format!("{} collisions, {:.4} expected", collisions, expected),
+        );
+        vec![collisions, distribution]
+    }
+
+    /// Hash every key, with seed 0, and test the hashes.
+    fn keys(&self, test: String, keys: impl Iterator<Item = Vec<u8>>) -> Vec<Outcome> {
+        let hashes = keys.map(|k| self.hash(&k, 0)).collect();
+        self.keyset(test, hashes)
+    }
+
+    /// `h` rotated right by `offset` within its `bits` bits.
+    fn rotate(&self, h: u64, offset: u32) -> u64 {
+        if offset == 0 {
+            h
+        } else {
+            ((h >> offset) | (h << (self.bits - offset))) & self.mask()
+        }
+    }
+
+    /// Keys made of a random block of 4 to 8 bytes repeated 8 times. The
+    /// first 4 bytes of each block are the key's index, so the keys are
+    /// distinct.
+    pub fn cyclic(&self) -> Vec<Outcome> {
+        let n = self.scaled(100_000, 1_000);
+        (4..=8)
+            .flat_map(|cycle| {
+                let mut rng = SplitMix64::new(cycle as u64);
+                let keys = (0..n as u32).map(move |i| {
+                    let mut block = random_key(&mut rng, cycle);
+                    block[..4].copy_from_slice(&i.to_le_bytes());
+                    block.repeat(8)
+                });
+                self.keys(format!("cyclic, {}-byte cycles", cycle), keys)
+            })
+            .collect()
+    }
+
+    /// All keys of 32 bits with at most 4 bits set, of 64 bits with at
+    /// most 3, and of 256 and 512 bits with at most 2.
+    pub fn sparse(&self) -> Vec<Outcome> {
+        let mut outcomes = Vec::new();
+        for &(bits, set) in &[(32, 4), (64, 3)] {
+            let keys = sparse_words(bits, set)
+                .into_iter()
+                .map(|w| w.to_le_bytes()[..bits as usize / 8].to_vec());
+            outcomes.extend(self.keys(format!("sparse, {}-bit keys, {} bits", bits, set), keys));
+        }
+        for &bits in &[256, 512] {
+            let mut keys = vec![vec![0u8; bits / 8]];
+            for i in 0..bits {
+                let mut key = vec![0u8; bits / 8];
+                flip(&mut key, i);
+                keys.push(key.clone());
+                for j in i + 1..bits {
+                    flip(&mut key, j);
+                    keys.push(key.clone());
+                    flip(&mut key, j);
+                }
+            }
+            outcomes.extend(self.keys(
+                format!("sparse, {}-bit keys, 2 bits", bits),
+                keys.into_iter(),
+            ));
+        }
+        outcomes
+    }
+
+    /// All 65536 keys of each length from 2 to 32 bytes that are zero
+    /// except for the first and last bytes.
+    pub fn two_bytes(&self) -> Vec<Outcome> {
+        [2, 3, 4, 8, 16, 32]
+            .iter()
+            .flat_map(|&len| {
+                let keys = (0..=u16::MAX).map(move |v| {
+                    let mut key = vec![0u8; len];
+                    key[0] = v as u8;
+                    key[len - 1] = (v >> 8) as u8;
+                    key
+                });
+                self.keys(format!("two bytes, {}-byte keys", len), keys)
+            })
+            .collect()
+    }
+
+    /// Every ordering of 8 distinct 4-byte blocks, differing in the low
+    /// bits, in the high bits, or in both.
+    pub fn permutation(&self) -> Vec<Outcome> {
+        // block i is i times a multiplier
+        let variants: [(&str, u32); 3] = [
+            ("low bits", 1),
+            ("high bits", 1 << 29),
+            ("high and low bits", (1 << 29) | 1),
+        ];
+        variants
+            .iter()
+            .flat_map(|&(name, multiplier)| {
+                let blocks: Vec<u32> = (0..8).map(|i| i * multiplier).collect();
+                let keys = permutations(&blocks).into_iter().map(|p| words_to_key(&p));
+                self.keys(format!("permutation, 8 blocks, {}", name), keys)
+            })
+            .collect()
+    }
+
+    /// Every sequence of 1 to 14 blocks, each one of two 4-byte blocks.
+    pub fn combination(&self) -> Vec<Outcome> {
+        let variants: [(&str, [u32; 2]); 3] = [
+            ("low bit", [0, 1]),
+            ("high bit", [0, 0x8000_0000]),
+            ("high and low bits", [1, 0x8000_0000]),
+        ];
+        variants
+            .iter()
+            .flat_map(|&(name, blocks)| {
+                let keys = (1..=14u32).flat_map(move |len| {
+                    (0..1u32 << len).map(move |choice| {
+                        let words: Vec<u32> = (0..len)
+                            .map(|i| blocks[(choice >> i) as usize & 1])
+                            .collect();
+                        words_to_key(&words)
+                    })
+                });
+                self.keys(format!("combination, 2 blocks, {}", name), keys)
+            })
+            .collect()
+    }
+
+    /// All values of a 12-bit window, in 8-byte keys that are otherwise
+    /// zero, at every position of the window. The result is that of the
+    /// worst position, corrected for the number of positions.
+    pub fn window(&self) -> Vec<Outcome> {
+        const WINDOW: u32 = 12;
+        let positions = 64 - WINDOW + 1;
+        let mut worst: Vec<Outcome> = Vec::new();
+        for position in 0..positions {
+            let keys = (0..1u64 << WINDOW).map(|v| (v << position).to_le_bytes().to_vec());
+            let outcomes = self.keys("window, 12 bits of 64".to_string(), keys);
+            if worst.is_empty() {
+                worst = outcomes;
+            } else {
+                for (w, o) in worst.iter_mut().zip(outcomes) {
+                    if o.p_value < w.p_value {
+                        *w = o;
+                        w.detail = format!("{} at bit {}", w.detail, position);
+                    }
+                }
+            }
+        }
+        for w in &mut worst {
+            w.p_value = (w.p_value * positions as f64).min(1.0);
+            w.passed = w.p_value >= self.alpha;
+        }
+        worst
+    }
+
+    /// Keys of text, "Foo" and "Bar" around, before or after every string
+    /// of 4 characters from "a" to "p".
+    pub fn text(&self) -> Vec<Outcome> {
+        let variants: [(&str, &str, &str); 3] = [
+            ("Foo????Bar", "Foo", "Bar"),
+            ("FooBar????", "FooBar", ""),
+            ("????FooBar", "", "FooBar"),
+        ];
+        variants
+            .iter()
+            .flat_map(|&(name, prefix, suffix)| {
+                let keys = (0..1u32 << 16).map(move |v| {
+                    let mut key = prefix.as_bytes().to_vec();
+                    key.extend((0..4).map(|i| b'a' + (v >> (4 * i)) as u8 % 16));
+                    key.extend(suffix.as_bytes());
+                    key
+                });
+                self.keys(format!("text, {}", name), keys)
+            })
+            .collect()
+    }
+
+    /// Keys of all zero bytes, of every length from 0 to 4096.
+    pub fn zeroes(&self) -> Vec<Outcome> {
+        let n = self.scaled(4096, 256);
+        let zeroes = vec![0u8; n];
+        self.keys(
+            "zero keys".to_string(),
+            (0..=n).map(|len| zeroes[..len].to_vec()),
+        )
+    }
+
+    /// One key hashed with many seeds: all seeds up to 100,000, and all
+    /// seeds with at most 2 bits set.
+    pub fn seed(&self) -> Vec<Outcome> {
+        let key = b"The quick brown fox jumps over the lazy dog";
+        let n = self.scaled(100_000, 1_000) as u64;
+        let mut outcomes = self.keyset(
+            "seeds, sequential".to_string(),
+            (0..n).map(|seed| self.hash(key, seed)).collect(),
+        );
+        outcomes.extend(
+            self.keyset(
+                "seeds, up to 2 bits".to_string(),
+                sparse_words(64, 2)
+                    .into_iter()
+                    .map(|seed| self.hash(key, seed))
+                    .collect(),
+            ),
+        );
+        outcomes
+    }
+}
+
+// ====================================
+// AvalancheMatrix
+
+/// How often each output bit flips when each input bit of a random key
+/// is flipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvalancheMatrix {
+    /// The length of the keys, in bytes.
+    pub key_len: usize,
+    /// The width of the hashes.
+    pub bits: u32,
+    /// The number of random keys.
+    pub trials: usize,
+    /// The number of flips of output bit j when input bit i was flipped,
+    /// at index i * `bits` + j.
+    pub flips: Vec<u64>,
+}
+
+impl AvalancheMatrix {
+    /// Flip each bit of `trials` random keys of `key_len` bytes,
+    /// generated from `seed`, and count the flips of the low `bits` bits
+    /// of `hash`.
+    pub fn new<F: Fn(&[u8]) -> u64>(
+        hash: F,
+        key_len: usize,
+        bits: u32,
+        trials: usize,
+        seed: u64,
+    ) -> AvalancheMatrix {
+        let mask = u64::MAX >> (64 - bits);
+        let mut rng = SplitMix64::new(seed);
+        let mut flips = vec![0u64; key_len * 8 * bits as usize];
+        for _ in 0..trials {
+            let mut key = random_key(&mut rng, key_len);
+            let h0 = hash(&key);
+            for i in 0..key_len * 8 {
+                flip(&mut key, i);
+                let mut d = (h0 ^ hash(&key)) & mask;
+                flip(&mut key, i);
+                while d != 0 {
+                    flips[i * bits as usize + d.trailing_zeros() as usize] += 1;
+                    d &= d - 1;
+                }
+            }
+        }
+        AvalancheMatrix {
+            key_len,
+            bits,
+            trials,
+            flips,
+        }
+    }
+
+    /// The number of input bits.
+    pub fn input_bits(&self) -> usize {
+        self.key_len * 8
+    }
+
+    /// The probability that flipping input bit `input` flips output bit
+    /// `output`, ideally 1/2.
+    pub fn probability(&self, input: usize, output: usize) -> f64 {
+        self.flips[input * self.bits as usize + output] as f64 / self.trials as f64
+    }
+}
+
+// ====================================
+// Key generation
+
+fn random_key(rng: &mut SplitMix64, len: usize) -> Vec<u8> {
+    let mut key = Vec::with_capacity(len + 8);
+    while key.len() < len {
+        key.extend_from_slice(&rng.next().to_le_bytes());
+    }
+    key.truncate(len);
+    key
+}
+
+/// Flip bit `i` of `key`.
+fn flip(key: &mut [u8], i: usize) {
+    key[i / 8] ^= 1 << (i % 8);
+}
+
+/// All words of `bits` bits with at most `set` bits set, in order of the
+/// number of bits set, starting with 0.
+fn sparse_words(bits: u32, set: u32) -> Vec<u64> {
+    let mut words = vec![0u64];
+    let mut previous = vec![(0u64, 0u32)];
+    for _ in 0..set {
+        // extend each word with a bit above its highest bit
+        let mut next = Vec::new();
+        for &(w, above) in &previous {
+            for b in above..bits {
+                next.push((w | (1 << b), b + 1));
+            }
+        }
+        words.extend(next.iter().map(|&(w, _)| w));
+        previous = next;
+    }
+    words
+}
+
+fn words_to_key(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+/// Every permutation of `items`, by Heap's algorithm.
+fn permutations(items: &[u32]) -> Vec<Vec<u32>> {
+    let mut items = items.to_vec();
+    let mut c = vec![0; items.len()];
+    let mut result = vec![items.clone()];
+    let mut i = 0;
+    while i < items.len() {
+        if c[i] < i {
+            items.swap(if i % 2 == 0 { 0 } else { c[i] }, i);
+            result.push(items.clone());
+            c[i] += 1;
+            i = 0;
+        } else {
+            c[i] = 0;
+            i += 1;
+        }
+    }
+    result
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod battery_tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(sparse_words(64, 3).len(), 1 + 64 + 2016 + 41664);
+        let mut words = sparse_words(32, 4);
+        assert!(words.iter().all(|w| w.count_ones() <= 4 && w >> 32 == 0));
+        words.sort();
+        words.dedup();
+        assert_eq!(words.len(), 1 + 32 + 496 + 4960 + 35960);
+        let mut perms = permutations(&[1, 2, 3, 4, 5]);
+        assert_eq!(perms.len(), 120);
+        perms.sort();
+        perms.dedup();
+        assert_eq!(perms.len(), 120);
+    }
+
+    #[test]
+    fn avalanche_matrix() {
+        // The first 4 bytes of the key, as a number: input bit i flips
+        // output bit i and nothing else.
+        let identity = |key: &[u8]| u32::from_le_bytes(key[..4].try_into().unwrap()) as u64;
+        let matrix = AvalancheMatrix::new(identity, 8, 32, 10, 0);
+        assert_eq!(matrix.input_bits(), 64);
+        for i in 0..64 {
+            for j in 0..32 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_eq!(matrix.probability(i, j), expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "jenkins")]
+    #[test]
+    fn spooky_passes() {
+        use crate::jenkins::spooky_hash::SpookyHasher;
+
+        let battery = Battery::for_hasher::<SpookyHasher>(64).with_scale(0.02);
+        for outcome in battery.run() {
+            assert!(outcome.passed, "{}", outcome);
+        }
+        let battery = Battery::for_hasher::<SpookyHasher>(32).with_scale(0.02);
+        for outcome in battery.run() {
+            assert!(outcome.passed, "{}", outcome);
+        }
+    }
+
+    #[cfg(feature = "oz")]
+    #[test]
+    fn loselose_fails() {
+        use crate::oz::LoseLoseHasher;
+
+        let battery = Battery::for_hasher::<LoseLoseHasher>(64).with_scale(0.02);
+        assert!(
+            battery
+                .avalanche()
+                .iter()
+                .all(|o| !o.passed && o.bias == 1.0)
+        );
+        assert!(!battery.differential()[0].passed);
+        assert!(battery.permutation().iter().all(|o| !o.passed));
+        assert!(battery.text().iter().all(|o| !o.passed));
+    }
+
+    #[cfg(feature = "jenkins")]
+    #[test]
+    fn seed_independence() {
+        use crate::jenkins::spooky_hash::spooky;
+
+        // a hash that ignores its seed
+        let battery = Battery::new(|key: &[u8], _| spooky(key), 64).with_scale(0.02);
+        let seed = battery.seed();
+        assert!(seed.iter().all(|o| !o.passed), "{:?}", seed);
+        assert!(battery.avalanche().iter().all(|o| o.passed));
+    }
+}
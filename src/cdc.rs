@@ -0,0 +1,358 @@
+//! Content-defined chunking with FastCDC, from Xia et al., *FastCDC: a
+//! Fast and Efficient Content-Defined Chunking Approach for Data
+//! Deduplication* (USENIX ATC 2016).
+//!
+//! Splitting a file into fixed-size blocks makes deduplication fragile: one
+//! inserted byte shifts every later block boundary. Content-defined
+//! chunking instead cuts wherever a rolling hash of the last few bytes
+//! matches a pattern, so boundaries move with the content and an edit
+//! only disturbs the chunks around it.
+//!
+//! FastCDC uses the [`Gear`] rolling hash and adds three things to the
+//! basic scheme:
+//! - no cut points are considered in the first `min_size` bytes of a chunk,
+//!   and the hash is not even computed there;
+//! - "normalized chunking": before `avg_size`, a mask with more bits
+//!   makes a cut less likely, and after it a mask with fewer bits makes
+//!   one more likely, which pulls chunk sizes towards the average;
+//! - a hard cut at `max_size`.
+//!
+//! Each chunk is reported with a 128-bit [`SpookyHasher`] fingerprint, for
+//! use as a deduplication key.
+
+use std::io::{self, Read};
+
+use crate::jenkins::spooky_hash::SpookyHasher;
+use crate::rolling::{GEAR_TABLE, Gear};
+
+/// Default minimum chunk size, 2 KiB.
+pub const MIN_SIZE: usize = 2 * 1024;
+/// Default target chunk size, 8 KiB.
+pub const AVG_SIZE: usize = 8 * 1024;
+/// Default maximum chunk size, 64 KiB.
+pub const MAX_SIZE: usize = 64 * 1024;
+
+/// A mask of the top `bits` bits of a word.
+///
+/// Gear shifts older bytes towards the top, so the high bits depend on
+/// the most input.
+#[inline(always)]
+const fn top_bits(bits: u32) -> u64 {
+    if bits == 0 { 0 } else { !0 << (64 - bits) }
+}
+
+/// A FastCDC chunker configuration.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct FastCdc {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+    // mask used before avg_size: harder to match
+    pub mask_s: u64,
+    // mask used after avg_size: easier to match
+    pub mask_l: u64,
+    pub table: &'static [u64; 256],
+}
+
+impl FastCdc {
+    /// A chunker with the given sizes and normalization level 2, which
+    /// the FastCDC paper recommends.
+    ///
+    /// The mask sizes use `log2(avg_size)`, rounded down, so `avg_size`
+    /// must be at least 8.
+    #[inline]
+    pub const fn new(min_size: usize, avg_size: usize, max_size: usize) -> FastCdc {
+        FastCdc::with_normalization(min_size, avg_size, max_size, 2)
+    }
+
+    /// A chunker with a specific normalization level: the masks have
+    /// `log2(avg_size) +/- level` bits. Level 0 is plain Gear-based CDC.
+    /// The level must be less than `log2(avg_size)`, or the mask used past
+    /// the average size has no bits and every position there is a cut
+    /// point.
+    #[inline]
+    pub const fn with_normalization(
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        level: u32,
+    ) -> FastCdc {
+        assert!(0 < min_size && min_size <= avg_size && avg_size <= max_size);
+        let bits = avg_size.ilog2();
+        assert!(level < bits && bits + level <= 64);
+        FastCdc {
+            min_size,
+            avg_size,
+            max_size,
+            mask_s: top_bits(bits + level),
+            mask_l: top_bits(bits - level),
+            table: &GEAR_TABLE,
+        }
+    }
+
+    /// Use a different Gear table, e.g. one from [`byte_table`] with a
+    /// secret seed, so that chunk boundaries do not leak content.
+    ///
+    /// [`byte_table`]: crate::rolling::byte_table
+    #[inline]
+    pub const fn with_table(mut self, table: &'static [u64; 256]) -> FastCdc {
+        self.table = table;
+        self
+    }
+
+    /// The length of the first chunk of `data`.
+    pub const fn cut(&self, data: &[u8]) -> usize {
+        let mut n = data.len();
+        if n <= self.min_size {
+            return n;
+        }
+        if n > self.max_size {
+            n = self.max_size;
+        }
+        let center = if n < self.avg_size { n } else { self.avg_size };
+        let mut gear = Gear::with_table(64, self.table);
+        let mut i = self.min_size;
+        while i < center {
+            gear.push(data[i]);
+            if gear.hash() & self.mask_s == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        while i < n {
+            gear.push(data[i]);
+            if gear.hash() & self.mask_l == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        n
+    }
+
+    /// Split `data` into chunks.
+    #[inline]
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> Chunks<'a> {
+        Chunks {
+            cdc: *self,
+            data,
+            offset: 0,
+        }
+    }
+
+    /// Split everything read from `reader` into chunks. At most
+    /// `max_size` bytes are buffered.
+    #[inline]
+    pub fn stream_chunks<R: Read>(&self, reader: R) -> StreamChunks<R> {
+        StreamChunks {
+            cdc: *self,
+            reader,
+            buffer: Vec::with_capacity(self.max_size),
+            offset: 0,
+            eof: false,
+        }
+    }
+}
+
+impl Default for FastCdc {
+    #[inline]
+    fn default() -> FastCdc {
+        FastCdc::new(MIN_SIZE, AVG_SIZE, MAX_SIZE)
+    }
+}
+
+/// The 128-bit SpookyHash of a chunk.
+#[inline]
+pub const fn fingerprint(data: &[u8]) -> (u64, u64) {
+    let mut h = SpookyHasher::default();
+    h.write(data);
+    h.finish128()
+}
+
+/// A chunk boundary and fingerprint.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Hash)]
+pub struct Chunk {
+    /// Position of the chunk's first byte in the input.
+    pub offset: u64,
+    pub length: usize,
+    pub fingerprint: (u64, u64),
+}
+
+/// An iterator over the chunks of a byte slice.
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    cdc: FastCdc,
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        let rest = &self.data[self.offset..];
+        if rest.is_empty() {
+            return None;
+        }
+        let length = self.cdc.cut(rest);
+        let chunk = Chunk {
+            offset: self.offset as u64,
+            length,
+            fingerprint: fingerprint(&rest[..length]),
+        };
+        self.offset += length;
+        Some(chunk)
+    }
+}
+
+/// An iterator over the chunks of a reader, yielding each chunk along
+/// with its contents.
+#[derive(Debug)]
+pub struct StreamChunks<R> {
+    cdc: FastCdc,
+    reader: R,
+    buffer: Vec<u8>,
+    offset: u64,
+    eof: bool,
+}
+
+impl<R: Read> StreamChunks<R> {
+    /// Read until the buffer holds `max_size` bytes or the input ends.
+    fn fill(&mut self) -> io::Result<()> {
+        let max = self.cdc.max_size;
+        while !self.eof && self.buffer.len() < max {
+            let start = self.buffer.len();
+            self.buffer.resize(max, 0);
+            match self.reader.read(&mut self.buffer[start..]) {
+                Ok(0) => {
+                    self.buffer.truncate(start);
+                    self.eof = true;
+                }
+                Ok(n) => self.buffer.truncate(start + n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                    self.buffer.truncate(start);
+                }
+                Err(e) => {
+                    self.buffer.truncate(start);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for StreamChunks<R> {
+    type Item = io::Result<(Chunk, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.fill() {
+            return Some(Err(e));
+        }
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let length = self.cdc.cut(&self.buffer);
+        let rest = self.buffer.split_off(length);
+        let data = std::mem::replace(&mut self.buffer, rest);
+        let chunk = Chunk {
+            offset: self.offset,
+            length,
+            fingerprint: fingerprint(&data),
+        };
+        self.offset += length as u64;
+        Some(Ok((chunk, data)))
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod cdc_tests {
+    use super::*;
+    use crate::mixers::SplitMix64;
+
+    const SMALL: FastCdc = FastCdc::new(64, 256, 1024);
+
+    fn random_bytes(n: usize, seed: u64) -> Vec<u8> {
+        let mut rng = SplitMix64::new(seed);
+        (0..n).map(|_| rng.next() as u8).collect()
+    }
+
+    #[test]
+    fn boundaries() {
+        let data = random_bytes(64 * 1024, 1);
+        let chunks: Vec<Chunk> = SMALL.chunks(&data).collect();
+        let mut offset = 0;
+        for (i, c) in chunks.iter().enumerate() {
+            assert_eq!(c.offset, offset as u64);
+            assert!(c.length <= SMALL.max_size);
+            assert!(c.length >= SMALL.min_size || i == chunks.len() - 1);
+            assert_eq!(c.fingerprint, fingerprint(&data[offset..offset + c.length]));
+            offset += c.length;
+        }
+        assert_eq!(offset, data.len());
+        // Normalized chunking keeps the mean near the target.
+        let mean = data.len() / chunks.len();
+        assert!(128 < mean && mean < 512, "mean chunk size {}", mean);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_mask() {
+        // log2(16) = 4 bits, and level 4 would leave mask_l no bits
+        FastCdc::with_normalization(4, 16, 64, 4);
+    }
+
+    #[test]
+    fn short_input() {
+        assert_eq!(SMALL.chunks(b"").count(), 0);
+        let chunks: Vec<Chunk> = SMALL.chunks(b"abc").collect();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].length, 3);
+        assert_eq!(chunks[0].fingerprint, fingerprint(b"abc"));
+        // With min_size == max_size, FastCDC degenerates to fixed-size
+        // blocks.
+        let fixed = FastCdc::new(1024, 1024, 1024);
+        let lengths: Vec<usize> = fixed.chunks(&[0u8; 2500]).map(|c| c.length).collect();
+        assert_eq!(lengths, vec![1024, 1024, 452]);
+    }
+
+    #[test]
+    fn content_defined() {
+        // An insertion near the start only disturbs the chunks around it.
+        let data = random_bytes(64 * 1024, 2);
+        let mut edited = data.clone();
+        edited.insert(100, 0x5a);
+        let before: Vec<_> = SMALL.chunks(&data).map(|c| c.fingerprint).collect();
+        let after: Vec<_> = SMALL.chunks(&edited).map(|c| c.fingerprint).collect();
+        let shared = after.iter().filter(|f| before.contains(f)).count();
+        assert!(shared + 3 >= before.len());
+    }
+
+    /// A reader that returns at most 7 bytes at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn stream() {
+        let data = random_bytes(16 * 1024 + 17, 3);
+        let slice: Vec<Chunk> = SMALL.chunks(&data).collect();
+        let stream: Vec<(Chunk, Vec<u8>)> = SMALL
+            .stream_chunks(Trickle(&data))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(slice.len(), stream.len());
+        for (c, (s, bytes)) in slice.iter().zip(stream.iter()) {
+            assert_eq!(c, s);
+            assert_eq!(&data[c.offset as usize..][..c.length], &bytes[..]);
+        }
+    }
+}
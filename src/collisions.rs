@@ -0,0 +1,330 @@
+//! Collision counts against the birthday bound.
+//!
+//! Does a Hasher, truncated to its low b bits as a hash table would
+//! truncate it, collide more often on real keys than a random function
+//! would? For each width from [`MIN_BITS`] to 64 bits, [`CollisionReport`]
+//! compares the observed number of collisions with the expectation for
+//! n random values, n - m (1 - (1 - 1/m)^n) for m = 2^b, and flags widths
+//! where the excess is significant. (To test the high bits instead, as
+//! SwissTable's control bytes use them, count `hash.reverse_bits()`.)
+//!
+//! The keys are streamed: only their hashes are kept, 8 bytes each, and
+//! all the widths are counted from a single sort. Sorting the hashes by
+//! their bit-reversed value puts hashes that share their low b bits next
+//! to each other, for every b at once, so the collisions at width b are
+//! the neighbours sharing at least b low bits. For more keys than fit in
+//! memory, [`count_partitioned`] makes 2^p passes over the keys, keeping
+//! only the hashes whose low p bits are the pass number; hashes that
+//! differ in those bits cannot collide at any width of p bits or more, so
+//! the passes' counts add up.
+
+use core::fmt;
+
+use crate::quality::{
+    collision_variance, expected_collisions, normal_upper_tail, poisson_upper_tail,
+};
+
+/// The narrowest width counted.
+pub const MIN_BITS: u32 = 16;
+
+/// Hashes seen, and kept for counting: all of them, or those in one
+/// partition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollisionCounter {
+    partition_bits: u32,
+    partition: u64,
+    keys: u64,
+    /// The bit-reversed hashes in the partition.
+    reversed: Vec<u64>,
+}
+
+impl CollisionCounter {
+    /// A counter keeping every hash.
+    pub fn new() -> CollisionCounter {
+        CollisionCounter::for_partition(0, 0)
+    }
+
+    /// A counter keeping only the hashes whose low `bits` bits are
+    /// `partition`.
+    pub fn for_partition(bits: u32, partition: u64) -> CollisionCounter {
+        assert!(bits <= MIN_BITS, "partition wider than the narrowest width");
+        assert!(partition >> bits == 0, "partition outside the bits");
+        CollisionCounter {
+            partition_bits: bits,
+            partition,
+            keys: 0,
+            reversed: Vec::new(),
+        }
+    }
+
+    /// Count one key's hash.
+    #[inline]
+    pub fn push(&mut self, hash: u64) {
+        self.keys += 1;
+        let mask = (1u64 << self.partition_bits) - 1;
+        if hash & mask == self.partition {
+            self.reversed.push(hash.reverse_bits());
+        }
+    }
+
+    /// The number of keys seen, in or out of the partition.
+    pub fn keys(&self) -> u64 {
+        self.keys
+    }
+
+    /// The collisions among the kept hashes at each width: element b is
+    /// the number of hashes whose low b bits are those of an earlier one.
+    pub fn collisions(mut self) -> [u64; 65] {
+        self.reversed.sort_unstable();
+        // shared[b]: the neighbours sharing exactly b low bits
+        let mut shared = [0u64; 65];
+        for pair in self.reversed.windows(2) {
+            shared[(pair[0] ^ pair[1]).leading_zeros() as usize] += 1;
+        }
+        let mut collisions = [0u64; 65];
+        let mut at_least = 0;
+        for b in (0..=64).rev() {
+            at_least += shared[b];
+            collisions[b] = at_least;
+        }
+        collisions
+    }
+}
+
+impl Default for CollisionCounter {
+    fn default() -> CollisionCounter {
+        CollisionCounter::new()
+    }
+}
+
+impl Extend<u64> for CollisionCounter {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, hashes: I) {
+        for hash in hashes {
+            self.push(hash);
+        }
+    }
+}
+
+/// The collisions at one width.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WidthCollisions {
+    pub bits: u32,
+    pub observed: u64,
+    pub expected: f64,
+    /// The probability that a random function has at least `observed`
+    /// collisions: Poisson while few are expected, and normal, with the
+    /// exact variance, otherwise.
+    pub p_value: f64,
+}
+
+impl WidthCollisions {
+    pub fn new(keys: u64, bits: u32, observed: u64) -> WidthCollisions {
+        let expected = expected_collisions(keys as usize, bits);
+        let p_value = if expected < 100.0 {
+            poisson_upper_tail(observed, expected)
+        } else {
+            let sd = collision_variance(keys as usize, bits).sqrt();
+            // with a continuity correction
+            normal_upper_tail((observed as f64 - 0.5 - expected) / sd)
+        };
+        WidthCollisions {
+            bits,
+            observed,
+            expected,
+            p_value,
+        }
+    }
+
+    /// The observed collisions over the expected.
+    pub fn ratio(&self) -> f64 {
+        self.observed as f64 / self.expected
+    }
+}
+
+impl fmt::Display for WidthCollisions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>2} bits: {:>10} collisions, expected {:>12.1}, ratio {:>7.3}, p = {:.4}",
+            self.bits,
+            self.observed,
+            self.expected,
+            self.ratio(),
+            self.p_value
+        )
+    }
+}
+
+/// Observed and expected collisions at every width from [`MIN_BITS`] to
+/// 64.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollisionReport {
+    pub keys: u64,
+    pub widths: Vec<WidthCollisions>,
+}
+
+impl CollisionReport {
+    /// The report for `keys` keys, from the [`CollisionCounter::collisions`]
+    /// of all of them, or the sums over all partitions.
+    pub fn new(keys: u64, collisions: &[u64; 65]) -> CollisionReport {
+        let widths = (MIN_BITS..=64)
+            .map(|b| WidthCollisions::new(keys, b, collisions[b as usize]))
+            .collect();
+        CollisionReport { keys, widths }
+    }
+
+    /// The widths with significantly more collisions than a random
+    /// function: p-values below `alpha` divided by the number of widths,
+    /// so that a random function has at most probability `alpha` of any.
+    pub fn excesses(&self, alpha: f64) -> Vec<WidthCollisions> {
+        let alpha = alpha / self.widths.len() as f64;
+        self.widths
+            .iter()
+            .filter(|w| w.p_value < alpha)
+            .copied()
+            .collect()
+    }
+
+    /// Whether no width has significantly more collisions than a random
+    /// function, at level `alpha`.
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.excesses(alpha).is_empty()
+    }
+}
+
+impl fmt::Display for CollisionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} keys", self.keys)?;
+        for w in &self.widths {
+            write!(f, "\n{}", w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Count the collisions among the hashes of `keys`, in one pass, keeping
+/// 8 bytes per key.
+pub fn count<F, I>(hash: F, keys: I) -> CollisionReport
+where
+    F: Fn(&[u8]) -> u64,
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut counter = CollisionCounter::new();
+    counter.extend(keys.into_iter().map(|k| hash(k.as_ref())));
+    let keys = counter.keys();
+    CollisionReport::new(keys, &counter.collisions())
+}
+
+/// Count the collisions among the hashes of the keys from `keys`, in
+/// 2^`partition_bits` passes, keeping 8 bytes per key per 2^`partition_bits`
+/// keys. `keys` must return the same keys each time.
+pub fn count_partitioned<F, G, I>(hash: F, keys: G, partition_bits: u32) -> CollisionReport
+where
+    F: Fn(&[u8]) -> u64,
+    G: Fn() -> I,
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut total = [0u64; 65];
+    let mut n = None;
+    for partition in 0..1u64 << partition_bits {
+        let mut counter = CollisionCounter::for_partition(partition_bits, partition);
+        counter.extend(keys().into_iter().map(|k| hash(k.as_ref())));
+        assert!(
+            n.is_none_or(|n| n == counter.keys()),
+            "keys differ between passes"
+        );
+        n = Some(counter.keys());
+        for (t, c) in total.iter_mut().zip(counter.collisions()) {
+            *t += c;
+        }
+    }
+    CollisionReport::new(n.unwrap_or(0), &total)
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod collisions_tests {
+    use super::*;
+    use crate::mixers::SplitMix64;
+
+    #[test]
+    fn counting() {
+        let mut counter = CollisionCounter::new();
+        counter.extend([0x1_0005, 0x2_0005, 0x1_0005, 0x5, 0x7]);
+        assert_eq!(counter.keys(), 5);
+        let collisions = counter.collisions();
+        // 0x7 differs from the others in bit 1
+        assert_eq!(collisions[0], 4);
+        assert_eq!(collisions[1], 4);
+        assert_eq!(collisions[2], 3);
+        assert_eq!(collisions[16], 3);
+        assert_eq!(collisions[17], 2);
+        assert_eq!(collisions[18], 1);
+        assert_eq!(collisions[64], 1);
+
+        let mut counter = CollisionCounter::for_partition(1, 1);
+        counter.extend([0x1_0005, 0x2_0005, 0x6, 0x6]);
+        assert_eq!(counter.keys(), 4);
+        assert_eq!(counter.collisions()[16], 1);
+    }
+
+    #[test]
+    fn random() {
+        let hashes: Vec<u64> = {
+            let mut rng = SplitMix64::new(1);
+            (0..1 << 18).map(|_| rng.next()).collect()
+        };
+        let mut counter = CollisionCounter::new();
+        counter.extend(hashes.iter().copied());
+        let report = CollisionReport::new(counter.keys(), &counter.collisions());
+        assert!(report.passes(0.01), "{}", report);
+        let w = report.widths[32 - MIN_BITS as usize];
+        assert_eq!(w.bits, 32);
+        // about 2^36 / 2^33 = 8
+        assert!((w.expected - 8.0).abs() < 0.01);
+        let w = report.widths[0];
+        assert!((w.observed as f64 - w.expected).abs() < 100.0);
+
+        // the same counts in 4 passes
+        let keys = || hashes.iter().map(|h| h.to_le_bytes());
+        let hash = |k: &[u8]| u64::from_le_bytes(k.try_into().unwrap());
+        assert_eq!(count_partitioned(hash, keys, 2), count(hash, keys()));
+    }
+
+    #[test]
+    fn excess() {
+        // 2^18 hashes with only 2^17 distinct values
+        let mut rng = SplitMix64::new(2);
+        let mut counter = CollisionCounter::new();
+        for _ in 0..1 << 17 {
+            let h = rng.next();
+            counter.extend([h, h]);
+        }
+        let report = CollisionReport::new(counter.keys(), &counter.collisions());
+        let excesses = report.excesses(0.01);
+        assert!(excesses.iter().any(|w| w.bits == 64));
+        assert!(excesses.iter().any(|w| w.bits == 24));
+        assert!(!report.passes(0.01));
+    }
+
+    #[cfg(all(feature = "fnv", feature = "jenkins", feature = "keysets"))]
+    #[test]
+    fn hashers() {
+        use crate::fnv::{fnv1a32, fnv1a64};
+        use crate::jenkins::spooky_hash::spooky;
+        use crate::keysets;
+
+        let keys = keysets::words();
+        assert!(count(fnv1a64, &keys).passes(0.01));
+        assert!(count(spooky, &keys).passes(0.01));
+        // the upper 32 bits are zero, so the keys colliding at 32 bits
+        // still collide at 64, where a random function has none
+        let report = count(fnv1a32, &keys);
+        let (w32, w64) = (report.widths[32 - 16], report.widths[64 - 16]);
+        assert_eq!(w32.observed, w64.observed);
+        assert!(!report.passes(0.01));
+    }
+}
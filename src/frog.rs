@@ -0,0 +1,259 @@
+//! Bob Jenkins' frog.c test: the first collision among evil keys.
+//!
+//! From the `Lookup3Hasher` documentation:
+//!
+//! > The most evil set of keys I know of are sets of keys that are all
+//! > the same length, with all bytes zero, except with a few bits set.
+//! > This is tested by frog.c.. To be even more evil, I had my hashes
+//! > return b and c instead of just c, yielding a 64-bit hash value.
+//!
+//! [`FrogKeys`] generates those keys, and [`first_collision`] hashes them
+//! until two collide and compares the number of keys hashed with the
+//! birthday expectation, about sqrt(π m / 2) keys or π m / 4 keypairs for
+//! m possible hash values. Bob reports lookup3's first 64-bit collision
+//! beyond 2^63 keypairs, which takes around 2^32 keys and a lot of memory.
+//! On a laptop, [`frog`] instead looks for collisions in windows of fewer
+//! bits, at offsets covering all 64 bits of the hash; a random function
+//! collides in a 32-bit window after about 82,000 keys.
+
+use core::fmt;
+use std::collections::HashMap;
+
+/// All keys of `len` bytes that are zero except for at most `max_set`
+/// bits, in order of the number of bits set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrogKeys {
+    len: usize,
+    max_set: usize,
+    /// The positions of the set bits of the next key, in increasing
+    /// order, or None when all keys have been generated.
+    positions: Option<Vec<usize>>,
+}
+
+impl FrogKeys {
+    pub fn new(len: usize, max_set: usize) -> FrogKeys {
+        assert!(max_set <= len * 8, "more bits set than in the key");
+        FrogKeys {
+            len,
+            max_set,
+            positions: Some(Vec::new()),
+        }
+    }
+
+    /// The number of keys, sum C(8 len, k) for k up to `max_set`.
+    pub fn total(&self) -> u64 {
+        let n = (self.len * 8) as u64;
+        let mut total = 0u64;
+        let mut binomial = 1u64;
+        for k in 0..=self.max_set as u64 {
+            total = total.saturating_add(binomial);
+            binomial = binomial.saturating_mul(n - k) / (k + 1);
+        }
+        total
+    }
+}
+
+impl Iterator for FrogKeys {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        let positions = self.positions.as_mut()?;
+        let mut key = vec![0u8; self.len];
+        for &p in positions.iter() {
+            key[p / 8] |= 1 << (p % 8);
+        }
+        // the next combination of the same size, or the first of the next
+        let bits = self.len * 8;
+        let k = positions.len();
+        match (0..k).rev().find(|&i| positions[i] < bits - k + i) {
+            Some(i) => {
+                positions[i] += 1;
+                for j in i + 1..k {
+                    positions[j] = positions[j - 1] + 1;
+                }
+            }
+            None if k < self.max_set => *positions = (0..=k).collect(),
+            None => self.positions = None,
+        }
+        Some(key)
+    }
+}
+
+/// The first collision in one window of hash bits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FirstCollision {
+    /// The lowest bit of the window.
+    pub offset: u32,
+    /// The width of the window.
+    pub bits: u32,
+    /// The number of keys hashed: up to and including the first that
+    /// collided, or all of them if none did.
+    pub keys: u64,
+    /// The indexes of the two keys that collided, if any did.
+    pub collision: Option<(u64, u64)>,
+    /// The expected number of keys up to the first collision for a random
+    /// function, sqrt(π m / 2) + 2/3 for m = 2^`bits`.
+    pub expected: f64,
+    /// The probability that a random function collides within `keys`
+    /// keys, if these did; otherwise 1.
+    pub p_value: f64,
+}
+
+impl FirstCollision {
+    /// The number of keypairs compared, n (n - 1) / 2 for n keys.
+    pub fn keypairs(&self) -> f64 {
+        let n = self.keys as f64;
+        n * (n - 1.0) / 2.0
+    }
+
+    /// Whether the collision, if any, came no earlier than a random
+    /// function's would with probability `alpha`.
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+impl fmt::Display for FirstCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bits {:>2}..{:<2}: ",
+            self.offset,
+            self.offset + self.bits
+        )?;
+        match self.collision {
+            Some((earlier, later)) => write!(
+                f,
+                "first collision at key {} (with key {}), 2^{:.1} keypairs",
+                later,
+                earlier,
+                self.keypairs().log2()
+            )?,
+            None => write!(f, "no collision in {} keys", self.keys)?,
+        }
+        write!(
+            f,
+            "; expected at key {:.0}, p = {:.4}",
+            self.expected, self.p_value
+        )
+    }
+}
+
+/// The first collision among `hashes` in the window of `bits` bits
+/// starting at bit `offset`.
+pub fn first_collision(hashes: &[u64], offset: u32, bits: u32) -> FirstCollision {
+    assert!(bits >= 1 && offset + bits <= 64, "window outside the hash");
+    let mask = u64::MAX >> (64 - bits);
+    let mut seen: HashMap<u64, u64> = HashMap::with_capacity(hashes.len());
+    let mut collision = None;
+    for (i, &h) in hashes.iter().enumerate() {
+        if let Some(&earlier) = seen.get(&((h >> offset) & mask)) {
+            collision = Some((earlier, i as u64));
+            break;
+        }
+        seen.insert((h >> offset) & mask, i as u64);
+    }
+    let keys = collision.map_or(hashes.len() as u64, |(_, later)| later + 1);
+    let m = 2f64.powi(bits as i32);
+    FirstCollision {
+        offset,
+        bits,
+        keys,
+        collision,
+        expected: (core::f64::consts::PI * m / 2.0).sqrt() + 2.0 / 3.0,
+        // P(no collision in n keys) is about exp(-n (n - 1) / 2m)
+        p_value: match collision {
+            Some(_) => -(-(keys as f64) * (keys as f64 - 1.0) / (2.0 * m)).exp_m1(),
+            None => 1.0,
+        },
+    }
+}
+
+/// The frog test, scaled down: hash up to `limit` keys of `len` bytes
+/// with at most `max_set` bits set, and find the first collision in each
+/// window of `bits` bits, at offsets every `bits / 2` bits across the
+/// 64-bit hash.
+pub fn frog<F: Fn(&[u8]) -> u64>(
+    hash: F,
+    len: usize,
+    max_set: usize,
+    limit: usize,
+    bits: u32,
+) -> Vec<FirstCollision> {
+    assert!((2..=64).contains(&bits));
+    let hashes: Vec<u64> = FrogKeys::new(len, max_set)
+        .take(limit)
+        .map(|k| hash(&k))
+        .collect();
+    let step = (bits / 2) as usize;
+    (0..=64 - bits)
+        .step_by(step)
+        .map(|offset| first_collision(&hashes, offset, bits))
+        .collect()
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod frog_tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        let keys: Vec<Vec<u8>> = FrogKeys::new(2, 2).collect();
+        assert_eq!(keys.len(), 1 + 16 + 120);
+        assert_eq!(FrogKeys::new(2, 2).total(), 137);
+        assert_eq!(keys[0], [0, 0]);
+        assert_eq!(keys[1], [1, 0]);
+        assert_eq!(keys[16], [0, 0x80]);
+        assert_eq!(keys[17], [3, 0]);
+        assert_eq!(keys[136], [0, 0xc0]);
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), keys.len());
+        assert_eq!(FrogKeys::new(16, 3).total(), 1 + 128 + 8128 + 341376);
+        assert_eq!(FrogKeys::new(1, 8).total(), 256);
+        assert_eq!(FrogKeys::new(1, 8).count(), 256);
+    }
+
+    #[test]
+    fn collisions() {
+        let hashes = [5, 6, 7, 0x1_0000_0006, 8];
+        let c = first_collision(&hashes, 0, 32);
+        assert_eq!(c.collision, Some((1, 3)));
+        assert_eq!(c.keys, 4);
+        assert_eq!(c.keypairs(), 6.0);
+        assert!(c.p_value < 1e-8);
+        assert!(!c.passes(0.001));
+        let c = first_collision(&hashes, 32, 32);
+        assert_eq!(c.collision, Some((0, 1)));
+        let c = first_collision(&hashes, 0, 64);
+        assert_eq!(c.collision, None);
+        assert_eq!(c.keys, 5);
+        assert!(c.passes(0.001));
+        // sqrt(π 2^32 / 2) is about 82137
+        assert!((first_collision(&[], 0, 32).expected - 82137.9).abs() < 1.0);
+    }
+
+    #[cfg(feature = "jenkins")]
+    #[test]
+    fn lookup3() {
+        use crate::jenkins::lookup3;
+
+        for c in frog(lookup3, 16, 3, 200_000, 32) {
+            assert!(c.passes(0.001), "{}", c);
+        }
+    }
+
+    #[cfg(feature = "oz")]
+    #[test]
+    fn loselose() {
+        use crate::oz::loselose;
+
+        for c in frog(loselose, 16, 3, 200_000, 32) {
+            assert!(c.keys < 20, "{}", c);
+            assert!(!c.passes(0.001), "{}", c);
+        }
+    }
+}
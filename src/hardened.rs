@@ -0,0 +1,410 @@
+//! Hardening fast Hashers against hash flooding.
+//!
+//! A hash-flooding attack sends a map many keys that land in the same
+//! bucket, turning O(1) operations into O(n). Against a fixed, public hash
+//! function the attacker can find such keys offline: for a table with
+//! 2^b buckets, about 2^b trial hashes per key. [`Hardened<H>`] makes
+//! that search useless by keying `H` with a secret:
+//!
+//! 1. the 128-bit key is written into `H` as a prefix before any data, so
+//!    the state `H` starts from is secret; and
+//! 2. `H`'s result is run through SipHash-1-3 keyed with the same key, so
+//!    the output bits used for bucket selection are a pseudorandom
+//!    function of `H`'s result.
+//!
+//! Without the key, keys chosen to share a bucket under the raw Hasher
+//! are spread like random keys (see the tests: 64 keys that all land in
+//! bucket 0 of 1024 under raw FNV-1a land in at least 56 distinct
+//! buckets when hardened, where random keys expect about 62).
+//!
+//! What this cannot fix is a Hasher whose *full-width* collisions do not
+//! depend on its starting state. Those collide before the final mix, and
+//! the mix maps equal inputs to equal outputs. For example:
+//! - `LoseLoseHasher` sums bytes, so any permutation of a key collides
+//!   with it, whatever the prefix;
+//! - `SDBMHasher` is `h * 65599 + c` modulo 2^32, so two keys of the same
+//!   length that collide from one state collide from every state, and a
+//!   birthday search finds such pairs in about 2^16 tries.
+//!
+//! For FNV-1a, Lookup3, OAAT and SpookyHash, collisions depend on the
+//! state, and the attacker has to find collisions of the whole 32- or
+//! 64-bit inner value blind, with no way to test candidates offline. A
+//! 32-bit inner Hasher still leaves about 2^16 keys for a birthday
+//! collision, so prefer 64-bit Hashers here. None of this is a
+//! cryptographic guarantee.
+
+use core::hash::{BuildHasher, Hasher};
+use core::marker::PhantomData;
+
+use crate::SeedableHasher;
+use crate::mixers::SplitMix64;
+
+// ====================================
+// SipHash-1-3
+
+#[inline(always)]
+const fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+/// SipHash-1-3, keyed with `(k0, k1)`, of the 8 little-endian bytes of
+/// `m`.
+pub const fn siphash13_u64(k0: u64, k1: u64, m: u64) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+    // the one full message block
+    v[3] ^= m;
+    sip_round(&mut v);
+    v[0] ^= m;
+    // the final block holds only the length, 8
+    let b = 8u64 << 56;
+    v[3] ^= b;
+    sip_round(&mut v);
+    v[0] ^= b;
+    v[2] ^= 0xff;
+    sip_round(&mut v);
+    sip_round(&mut v);
+    sip_round(&mut v);
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+// ====================================
+// Hardened
+
+/// A Hasher keyed with a secret prefix and finished with a keyed
+/// SipHash-1-3 mix.
+#[derive(PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+pub struct Hardened<H> {
+    pub inner: H,
+    k0: u64,
+    k1: u64,
+}
+
+impl<H: Hasher + Default> Hardened<H> {
+    /// Key a default `H` with `(k0, k1)`.
+    #[inline]
+    pub fn new(k0: u64, k1: u64) -> Hardened<H> {
+        let mut inner = H::default();
+        inner.write(&k0.to_le_bytes());
+        inner.write(&k1.to_le_bytes());
+        Hardened { inner, k0, k1 }
+    }
+}
+
+/// The key is the first two outputs of SplitMix64 from the seed. Seeds
+/// then carry only 64 bits of secret, not 128.
+impl<H: Hasher + Default> SeedableHasher for Hardened<H> {
+    #[inline]
+    fn with_seed(seed: u64) -> Hardened<H> {
+        let mut rng = SplitMix64::new(seed);
+        let k0 = rng.next();
+        Hardened::new(k0, rng.next())
+    }
+
+    #[inline]
+    fn with_seed128(seed: u128) -> Hardened<H> {
+        Hardened::new(seed as u64, (seed >> 64) as u64)
+    }
+}
+
+/// Keyed with seed 0. The key is public, so this gives no protection
+/// against flooding; use [`HardenedState::new`] or a secret seed.
+impl<H: Hasher + Default> Default for Hardened<H> {
+    #[inline]
+    fn default() -> Hardened<H> {
+        Hardened::with_seed(0)
+    }
+}
+
+impl<H: core::fmt::Debug> core::fmt::Debug for Hardened<H> {
+    // Do not print the key.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Hardened")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: Hasher> Hasher for Hardened<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        siphash13_u64(self.k0, self.k1, self.inner.finish())
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes);
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, i: u8) {
+        self.inner.write_u8(i);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, i: u16) {
+        self.inner.write_u16(i);
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, i: u32) {
+        self.inner.write_u32(i);
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, i: u64) {
+        self.inner.write_u64(i);
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, i: u128) {
+        self.inner.write_u128(i);
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, i: usize) {
+        self.inner.write_usize(i);
+    }
+}
+
+/// A `BuildHasher` for [`Hardened<H>`] with a fixed key.
+pub struct HardenedState<H> {
+    k0: u64,
+    k1: u64,
+    hasher: PhantomData<fn() -> H>,
+}
+
+impl<H> HardenedState<H> {
+    /// A builder with a secret key, drawn from the operating system's
+    /// randomness.
+    ///
+    /// Both halves of the key come from [`random_seed`], which derives
+    /// every seed from one 64-bit per-process seed, so the 128-bit key
+    /// has at most 64 bits of entropy. Use `with_keys` with 128 bits from
+    /// a secure source if that is not enough.
+    ///
+    /// [`random_seed`]: crate::random::random_seed
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn new() -> HardenedState<H> {
+        use crate::random::random_seed;
+        HardenedState::with_keys(random_seed(), random_seed())
+    }
+
+    /// A builder with a specific key. The key must be kept secret.
+    #[inline(always)]
+    pub const fn with_keys(k0: u64, k1: u64) -> HardenedState<H> {
+        HardenedState {
+            k0,
+            k1,
+            hasher: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H> Default for HardenedState<H> {
+    #[inline]
+    fn default() -> HardenedState<H> {
+        HardenedState::new()
+    }
+}
+
+impl<H: Hasher + Default> BuildHasher for HardenedState<H> {
+    type Hasher = Hardened<H>;
+
+    #[inline]
+    fn build_hasher(&self) -> Hardened<H> {
+        Hardened::new(self.k0, self.k1)
+    }
+}
+
+impl<H> Clone for HardenedState<H> {
+    #[inline]
+    fn clone(&self) -> HardenedState<H> {
+        *self
+    }
+}
+
+impl<H> Copy for HardenedState<H> {}
+
+impl<H> core::fmt::Debug for HardenedState<H> {
+    // Do not print the key.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HardenedState").finish_non_exhaustive()
+    }
+}
+
+// ------------------------------------
+
+#[cfg(all(test, feature = "fnv", feature = "oz", feature = "mixers"))]
+mod hardened_tests {
+    use super::*;
+    use crate::fnv::FNV1aHasher64;
+    use crate::oz::{LoseLoseHasher, SDBMHasher};
+    use std::collections::HashMap;
+
+    const KEYS: HardenedState<FNV1aHasher64> =
+        HardenedState::with_keys(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+
+    /// SipHash-c-d of `m`, following the reference implementation.
+    fn siphash(c: usize, d: usize, k0: u64, k1: u64, m: &[u8]) -> u64 {
+        let mut v = [
+            k0 ^ 0x736f6d6570736575,
+            k1 ^ 0x646f72616e646f6d,
+            k0 ^ 0x6c7967656e657261,
+            k1 ^ 0x7465646279746573,
+        ];
+        let blocks = m.chunks_exact(8);
+        let mut last = [0u8; 8];
+        last[..blocks.remainder().len()].copy_from_slice(blocks.remainder());
+        last[7] = m.len() as u8;
+        for block in blocks.map(|b| b.try_into().unwrap()).chain([last]) {
+            let b = u64::from_le_bytes(block);
+            v[3] ^= b;
+            for _ in 0..c {
+                sip_round(&mut v);
+            }
+            v[0] ^= b;
+        }
+        v[2] ^= 0xff;
+        for _ in 0..d {
+            sip_round(&mut v);
+        }
+        v[0] ^ v[1] ^ v[2] ^ v[3]
+    }
+
+    #[test]
+    fn siphash13() {
+        // The SipHash-2-4 vectors from the reference implementation, for
+        // the key 00 01 .. 0f and the messages 00 01 .. (len - 1).
+        let (k0, k1) = (0x0706050403020100, 0x0f0e0d0c0b0a0908);
+        let message: Vec<u8> = (0..16).collect();
+        for (len, expected) in [
+            (0, 0x726fdb47dd0e0e31),
+            (1, 0x74f839c593dc67fd),
+            (8, 0x93f5f5799a932462),
+            (15, 0xa129ca6149be45e5),
+        ] {
+            assert_eq!(siphash(2, 4, k0, k1, &message[..len]), expected);
+        }
+        // SipHash-1-3 is the same with fewer rounds.
+        for m in [0, 0x0706050403020100, 0x0123456789abcdef, u64::MAX] {
+            assert_eq!(
+                siphash13_u64(k0, k1, m),
+                siphash(1, 3, k0, k1, &m.to_le_bytes())
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn siphash13_matches_std() {
+        // std's DefaultHasher is SipHash-1-3, and `new()` uses the zero key.
+        use std::collections::hash_map::DefaultHasher;
+
+        for m in [0, 1, 0x0123456789abcdef, u64::MAX] {
+            let mut h = DefaultHasher::new();
+            h.write_u64(m);
+            assert_eq!(siphash13_u64(0, 0, m), h.finish());
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        let mut rng = SplitMix64::new(5);
+        let (k0, k1) = (rng.next(), rng.next());
+        let a = Hardened::<FNV1aHasher64>::with_seed(5);
+        assert_eq!(a, Hardened::new(k0, k1));
+        assert_eq!(
+            Hardened::<FNV1aHasher64>::with_seed128(k0 as u128 | (k1 as u128) << 64),
+            a
+        );
+        assert_eq!(Hardened::<FNV1aHasher64>::default(), Hardened::with_seed(0));
+        assert_ne!(Hardened::<FNV1aHasher64>::default(), a);
+    }
+
+    #[test]
+    fn basic() {
+        assert_eq!(KEYS.hash_one("abc"), KEYS.hash_one("abc"));
+        assert_ne!(KEYS.hash_one("abc"), KEYS.hash_one("abd"));
+        let other = HardenedState::<FNV1aHasher64>::with_keys(1, 2);
+        assert_ne!(KEYS.hash_one("abc"), other.hash_one("abc"));
+        let mut map: HashMap<&str, i32, HardenedState<FNV1aHasher64>> = HashMap::with_hasher(KEYS);
+        map.insert("one", 1);
+        assert_eq!(map.get("one"), Some(&1));
+    }
+
+    /// The number of distinct buckets, out of `2^bits`, used by `hashes`.
+    fn buckets(hashes: impl Iterator<Item = u64>, bits: u32) -> usize {
+        let mut used: Vec<u64> = hashes.map(|h| h & ((1 << bits) - 1)).collect();
+        used.sort_unstable();
+        used.dedup();
+        used.len()
+    }
+
+    #[test]
+    fn bucket_flooding() {
+        // The attacker's offline search: 64 keys whose raw FNV-1a hashes
+        // all land in bucket 0 of 1024.
+        let raw = |k: u64| {
+            let mut h = FNV1aHasher64::default();
+            h.write(&k.to_le_bytes());
+            h.finish()
+        };
+        let flood: Vec<u64> = (0u64..).filter(|&k| raw(k) & 1023 == 0).take(64).collect();
+        assert_eq!(buckets(flood.iter().map(|&k| raw(k)), 10), 1);
+        // Hardened, they are spread like random keys: 64 random keys in
+        // 1024 buckets expect about 62 distinct buckets.
+        let hardened = |k: &u64| {
+            let mut h = KEYS.build_hasher();
+            h.write(&k.to_le_bytes());
+            h.finish()
+        };
+        assert!(buckets(flood.iter().map(hardened), 10) >= 56);
+    }
+
+    #[test]
+    fn state_independent_collisions() {
+        // Permutations collide under LoseLose whatever the key.
+        let lose = HardenedState::<LoseLoseHasher>::with_keys(1, 2);
+        assert_eq!(lose.hash_one(b"ab"), lose.hash_one(b"ba"));
+
+        // A birthday search finds equal-length SDBM collisions from the
+        // default state, and they still collide when hardened.
+        let sdbm = |k: &[u8], h: &mut SDBMHasher| {
+            h.write(k);
+            h.finish()
+        };
+        let mut rng = crate::mixers::SplitMix64::new(0);
+        let mut seen = HashMap::new();
+        let (a, b) = core::iter::repeat_with(|| rng.next().to_le_bytes())
+            .find_map(|k| {
+                let k: [u8; 6] = k[..6].try_into().unwrap();
+                let h = sdbm(&k, &mut SDBMHasher::default());
+                seen.insert(h, k).map(|j| (j, k))
+            })
+            .unwrap();
+        assert_ne!(a, b);
+        let hardened = HardenedState::<SDBMHasher>::with_keys(3, 4);
+        let hash = |k: &[u8; 6]| {
+            let mut h = hardened.build_hasher();
+            h.write(k);
+            h.finish()
+        };
+        assert_eq!(hash(&a), hash(&b));
+    }
+}
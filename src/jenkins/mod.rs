@@ -25,7 +25,7 @@ pub mod spooky_hash;
 /// > Plumb. Colin ended up using an even simpler (and weaker) hash
 /// > that was sufficient for his purpose.
 #[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
-pub struct OAATHasher(pub u64);
+pub struct OAATHasher(pub u32);
 
 impl OAATHasher {
     #[inline(always)]
@@ -33,20 +33,26 @@ impl OAATHasher {
         OAATHasher(0)
     }
 
+    /// The low 32 bits of the seed are the initial state.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> OAATHasher {
+        OAATHasher(seed as u32)
+    }
+
     #[inline(always)]
     pub const fn finish(&self) -> u64 {
         let mut hash = self.0;
         hash = hash.wrapping_add(hash.wrapping_shl(3));
         hash ^= hash.wrapping_shr(11);
         hash = hash.wrapping_add(hash.wrapping_shl(15));
-        hash
+        hash as u64
     }
 
     #[inline(always)]
     pub const fn write(&mut self, bytes: &[u8]) {
         let mut i = 0;
         while i < bytes.len() {
-            self.0 = self.0.wrapping_add(bytes[i] as u64);
+            self.0 = self.0.wrapping_add(bytes[i] as u32);
             self.0 = self.0.wrapping_add(self.0.wrapping_shl(10));
             self.0 ^= self.0.wrapping_shr(6);
             i += 1;
@@ -55,12 +61,20 @@ impl OAATHasher {
 }
 
 duplicate_const_traits!(OAATHasher);
+seedable_hasher!(OAATHasher);
 hasher_to_fcn!(
     /// Provide access to OAATHasher in a single call.
     oaat,
     OAATHasher
 );
 
+finalized_hasher!(
+    /// `OAATHasher` through `fmix64`; see [`Finalized`](crate::mixers::Finalized).
+    OAATFinalized,
+    OAATHasher,
+    oaat_finalized
+);
+
 // ------------------------------------
 
 #[cfg(test)]
@@ -70,10 +84,10 @@ mod oaat_tests {
     #[test]
     fn basic() {
         assert_eq!(oaat(b""), 0);
-        assert_eq!(oaat(b"a"), 29161854018);
-        assert_eq!(oaat(b"b"), 30079156635);
-        assert_eq!(oaat(b"ab"), 30087418617432);
-        assert_eq!(oaat(b"abcdefg"), 3103867595652801641);
+        assert_eq!(oaat(b"a"), 0xca2e9442);
+        assert_eq!(oaat(b"b"), 14385563);
+        assert_eq!(oaat(b"ab"), 1172708952);
+        assert_eq!(oaat(b"abcdefg"), 1254556024);
     }
 }
 
@@ -148,6 +162,8 @@ const fn const_slice_window<T>(s: &[T], offset: usize, len: usize) -> &[T] {
 /// > beyond 263 keypairs, which is exactly what you'd expect from a
 /// > completely random mapping to 64-bit values.
 ///
+/// (The frog module has a scaled-down version of this test.)
+///
 /// This structure implements hashlittle2:
 ///
 /// > You probably want to use hashlittle(). hashlittle() and
@@ -185,6 +201,13 @@ impl Lookup3Hasher {
         Lookup3Hasher { pc, pb }
     }
 
+    /// The low 32 bits of the seed are `pc` and the high 32 bits are `pb`,
+    /// as in lookup3.c's `hashlittle2(key, length, &pc, &pb)`.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> Lookup3Hasher {
+        Lookup3Hasher::new(seed as u32, (seed >> 32) as u32)
+    }
+
     #[inline(always)]
     pub const fn oneshot(bytes: &[u8], pc: u32, pb: u32) -> u64 {
         let mut hasher = Lookup3Hasher::new(pc, pb);
@@ -259,6 +282,34 @@ impl Lookup3Hasher {
         *b = b.wrapping_add(*a);
     }
 
+    /// The inverse of `mix`: `unmix` undoes `mix`, step by step, in
+    /// reverse.
+    ///
+    /// Given the internal state after a block has been mixed, this
+    /// recovers the state before it, which makes it possible to work
+    /// backwards from a chosen state to the input that produces it.
+    #[inline(always)]
+    pub const fn unmix(a: &mut u32, b: &mut u32, c: &mut u32) {
+        *b = b.wrapping_sub(*a);
+        *c ^= rot(*b, 4);
+        *c = c.wrapping_add(*b);
+        *a = a.wrapping_sub(*c);
+        *b ^= rot(*a, 19);
+        *b = b.wrapping_add(*a);
+        *c = c.wrapping_sub(*b);
+        *a ^= rot(*c, 16);
+        *a = a.wrapping_add(*c);
+        *b = b.wrapping_sub(*a);
+        *c ^= rot(*b, 8);
+        *c = c.wrapping_add(*b);
+        *a = a.wrapping_sub(*c);
+        *b ^= rot(*a, 6);
+        *b = b.wrapping_add(*a);
+        *c = c.wrapping_sub(*b);
+        *a ^= rot(*c, 4);
+        *a = a.wrapping_add(*c);
+    }
+
     /// > final -- final mixing of 3 32-bit values (a,b,c) into c
     /// >
     /// > Pairs of (a,b,c) values differing in only a few bits will usually
@@ -322,11 +373,14 @@ impl Lookup3Hasher {
         self.pc = c;
     }
 
+    /// Each call to `write` is one call to `hashlittle2`, with the
+    /// previous result as the initial values. `hashlittle2` mixes the
+    /// length into its initial state, so it cannot stream: a key split
+    /// across several writes does not hash like the whole key, and
+    /// neither do `#[derive(Hash)]` types or `str`s, which `Hash` writes
+    /// in pieces. This is an open defect until the Hasher buffers the
+    /// key; hash whole keys with [`lookup3`] meanwhile.
     pub const fn write(&mut self, bytes: &[u8]) {
-        if bytes.is_empty() {
-            return;
-        }
-
         let initial = INIT_MAGIC
             .wrapping_add(bytes.len() as u32)
             .wrapping_add(self.pc);
@@ -335,95 +389,24 @@ impl Lookup3Hasher {
         let mut c: u32 = initial;
         c = c.wrapping_add(self.pb);
 
-        let mut bytes_cursor = bytes;
-
-        #[cfg(target_endian = "little")]
-        {
-            if (core::mem::align_of_val(bytes) & 3) == 0 {
-                while bytes_cursor.len() >= 12 {
-                    // true for all chunks except the last
-                    a = a.wrapping_add(load_int_le!(bytes_cursor, 0, u32));
-                    b = b.wrapping_add(load_int_le!(bytes_cursor, 4, u32));
-                    c = c.wrapping_add(load_int_le!(bytes_cursor, 8, u32));
-                    Self::mix(&mut a, &mut b, &mut c);
-                    bytes_cursor = const_slice_window(bytes_cursor, 12, bytes_cursor.len() - 12);
-                }
-
-                if bytes_cursor.len() >= 4 {
-                    a = a.wrapping_add(load_int_le!(bytes_cursor, 0, u32));
-
-                    if bytes_cursor.len() >= 8 {
-                        b = b.wrapping_add(load_int_le!(bytes_cursor, 4, u32));
-                        c = c.wrapping_add(Self::shift_add(const_slice_window(
-                            bytes_cursor,
-                            8,
-                            bytes_cursor.len() - 8,
-                        )));
-                    } else {
-                        b = b.wrapping_add(Self::shift_add(const_slice_window(
-                            bytes_cursor,
-                            4,
-                            bytes_cursor.len() - 4,
-                        )));
-                    }
-                } else {
-                    a = a.wrapping_add(Self::shift_add(bytes_cursor));
-                }
-                return self.finish_write(a, b, c);
-            } else if (core::mem::align_of_val(bytes) & 1) == 0 {
-                while bytes_cursor.len() >= 12 {
-                    // true for all chunks except the last
-                    a = a
-                        .wrapping_add(load_int_le!(bytes_cursor, 0, u16) as u32)
-                        .wrapping_add((load_int_le!(bytes_cursor, 0, u16) as u32) << 16);
-                    b = b
-                        .wrapping_add(load_int_le!(bytes_cursor, 4, u16) as u32)
-                        .wrapping_add((load_int_le!(bytes_cursor, 6, u16) as u32) << 16);
-                    c = c
-                        .wrapping_add(load_int_le!(bytes_cursor, 8, u16) as u32)
-                        .wrapping_add((load_int_le!(bytes_cursor, 10, u16) as u32) << 16);
-                    Self::mix(&mut a, &mut b, &mut c);
-                    bytes_cursor = const_slice_window(bytes_cursor, 12, bytes_cursor.len() - 12);
-                }
-                if bytes_cursor.len() >= 4 {
-                    a = a
-                        .wrapping_add(load_int_le!(bytes_cursor, 0, u16) as u32)
-                        .wrapping_add((load_int_le!(bytes_cursor, 0, u16) as u32) << 16);
-
-                    if bytes_cursor.len() >= 8 {
-                        b = b
-                            .wrapping_add(load_int_le!(bytes_cursor, 4, u16) as u32)
-                            .wrapping_add((load_int_le!(bytes_cursor, 6, u16) as u32) << 16);
-                        c = c.wrapping_add(Self::shift_add(const_slice_window(
-                            bytes_cursor,
-                            8,
-                            bytes_cursor.len() - 8,
-                        )));
-                    } else {
-                        b = b.wrapping_add(Self::shift_add(const_slice_window(
-                            bytes_cursor,
-                            4,
-                            bytes_cursor.len() - 4,
-                        )));
-                    }
-                } else {
-                    a = a.wrapping_add(Self::shift_add(bytes_cursor));
-                }
-                return self.finish_write(a, b, c);
-            }
+        if bytes.is_empty() {
+            // "zero length strings require no mixing"
+            self.pb = b;
+            self.pc = c;
+            return;
         }
 
-        // For big endian machines and unaligned slices: hash bytes.
-        // "You could implement hashbig2() if you wanted but I
-        // haven't bothered here."
-        while bytes_cursor.len() >= 12 {
+        // lookup3.c reads whole words from aligned keys on little-endian
+        // machines, but the result is the same as reading the key a byte
+        // at a time, which is all that can be done in a const fn.
+        //
+        // All but the last block are mixed; the last, of 1 to 12 bytes,
+        // goes straight to final_mix.
+        let mut bytes_cursor = bytes;
+        while bytes_cursor.len() > 12 {
             a = a.wrapping_add(Self::shift_add(const_slice_window(bytes_cursor, 0, 4)));
             b = b.wrapping_add(Self::shift_add(const_slice_window(bytes_cursor, 4, 4)));
-            c = c.wrapping_add(Self::shift_add(const_slice_window(
-                bytes_cursor,
-                8,
-                bytes_cursor.len() - 8,
-            )));
+            c = c.wrapping_add(Self::shift_add(const_slice_window(bytes_cursor, 8, 4)));
             Self::mix(&mut a, &mut b, &mut c);
             bytes_cursor = const_slice_window(bytes_cursor, 12, bytes_cursor.len() - 12);
         }
@@ -453,6 +436,7 @@ impl Lookup3Hasher {
 }
 
 duplicate_const_traits!(Lookup3Hasher);
+seedable_hasher!(Lookup3Hasher);
 hasher_to_fcn!(
     /// Provide access to Lookup3Hasher in a single call.
     lookup3,
@@ -465,13 +449,62 @@ hasher_to_fcn!(
 mod lookup3_tests {
     use super::*;
 
+    #[test]
+    fn seeded() {
+        assert_eq!(Lookup3Hasher::with_seed(0), Lookup3Hasher::default());
+        assert_eq!(
+            Lookup3Hasher::with_seed(0x2_0000_0001),
+            Lookup3Hasher::new(1, 2)
+        );
+        let h: Lookup3Hasher = crate::SeedableHasher::with_seed128(0x2_0000_0001 << 64);
+        assert_eq!(h, Lookup3Hasher::new(1, 2));
+    }
+
+    #[test]
+    fn seeded_type() {
+        use crate::Seeded;
+        use core::hash::{BuildHasher, BuildHasherDefault};
+
+        const H: Seeded<Lookup3Hasher, 0x1234> = Seeded::<Lookup3Hasher, 0x1234>::default();
+        assert_eq!(H.0, Lookup3Hasher::with_seed(0x1234));
+        const HASH: u64 = {
+            let mut h = Seeded::<Lookup3Hasher, 0x1234>::default();
+            h.write(b"abc");
+            h.finish()
+        };
+        let a = BuildHasherDefault::<Seeded<Lookup3Hasher, 0x1234>>::default();
+        let b = BuildHasherDefault::<Seeded<Lookup3Hasher, 0x5678>>::default();
+        assert_eq!(core::mem::size_of_val(&a), 0);
+        assert_eq!(a.hash_one(7u64), a.hash_one(7u64));
+        assert_ne!(a.hash_one(7u64), b.hash_one(7u64));
+        assert_eq!(HASH, Lookup3Hasher::oneshot(b"abc", 0x1234, 0));
+    }
+
+    #[test]
+    fn build_seed() {
+        use crate::{BUILD_SEED, BuildSeeded};
+
+        const H: Lookup3Hasher = Lookup3Hasher::with_build_seed();
+        assert_eq!(H, Lookup3Hasher::with_seed(BUILD_SEED));
+        assert_eq!(BuildSeeded::<Lookup3Hasher>::default().0, H);
+    }
+
     #[test]
     fn basic() {
-        assert_eq!(lookup3(b""), 0);
+        assert_eq!(lookup3(b""), 0xdeadbeefdeadbeef);
         assert_eq!(lookup3(b"a"), 6351843130003064584);
         assert_eq!(lookup3(b"b"), 5351957087540069269);
         assert_eq!(lookup3(b"ab"), 7744397999705663711);
         assert_eq!(lookup3(b"abcd"), 16288908501016938652);
         assert_eq!(lookup3(b"abcdefg"), 6461572128488215717);
     }
+
+    #[test]
+    fn unmix() {
+        let (mut a, mut b, mut c) = (0xdeadbeef_u32, 0x01234567_u32, 0x89abcdef_u32);
+        Lookup3Hasher::mix(&mut a, &mut b, &mut c);
+        assert_ne!((a, b, c), (0xdeadbeef, 0x01234567, 0x89abcdef));
+        Lookup3Hasher::unmix(&mut a, &mut b, &mut c);
+        assert_eq!((a, b, c), (0xdeadbeef, 0x01234567, 0x89abcdef));
+    }
 }
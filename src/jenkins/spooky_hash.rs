@@ -34,7 +34,7 @@ const SC_CONST: u64 = 0xdeadbeefdeadbeefu64;
 /// data indices: 0..11
 /// state indices: 0..11
 #[inline(always)]
-const fn mix(data: &[u64], state: &mut [u64; SC_NUM_VARS]) {
+pub const fn mix(data: &[u64], state: &mut [u64; SC_NUM_VARS]) {
     debug_assert!(data.len() >= 12);
     state[0] = state[0].wrapping_add(data[0]);
     state[2] ^= state[10];
@@ -98,6 +98,38 @@ const fn mix(data: &[u64], state: &mut [u64; SC_NUM_VARS]) {
     state[10] = state[10].wrapping_add(state[0]);
 }
 
+/// Rotation amounts of `mix`, one per state word.
+const MIX_ROTATIONS: [u32; SC_NUM_VARS] = [11, 32, 43, 31, 17, 28, 39, 57, 55, 54, 22, 46];
+
+/// The inverse of `mix`: given the state after `mix(data, state)` and the
+/// same data, restore the state from before.
+///
+/// `mix` is twelve copies of the same step, one per state word `i`:
+///
+/// ```text
+/// s[i] += data[i]; s[i+2] ^= s[i+10]; s[i+11] ^= s[i];
+/// s[i] = rot64(s[i], r[i]); s[i+11] += s[i+1];
+/// ```
+///
+/// with indices taken modulo 12. Each operation is individually
+/// reversible, so this runs them backwards.
+pub const fn unmix(data: &[u64], state: &mut [u64; SC_NUM_VARS]) {
+    debug_assert!(data.len() >= 12);
+    let mut i = SC_NUM_VARS;
+    while i > 0 {
+        i -= 1;
+        let i1 = (i + 1) % SC_NUM_VARS;
+        let i2 = (i + 2) % SC_NUM_VARS;
+        let i10 = (i + 10) % SC_NUM_VARS;
+        let i11 = (i + 11) % SC_NUM_VARS;
+        state[i11] = state[i11].wrapping_sub(state[i1]);
+        state[i] = state[i].rotate_right(MIX_ROTATIONS[i]);
+        state[i11] ^= state[i];
+        state[i2] ^= state[i10];
+        state[i] = state[i].wrapping_sub(data[i]);
+    }
+}
+
 /// > Mix all 12 inputs together so that h0, h1 are a hash of
 /// > them all.
 /// >
@@ -184,9 +216,8 @@ const fn end(data: &[u64; SC_NUM_VARS], state: &mut [u64; SC_NUM_VARS]) {
 /// > diffs with diffs defined by either xor or subtraction with
 /// > a base of all zeros plus a counter, or plus another bit,
 /// > or random
-
 #[inline(always)]
-const fn short_mix(h: &mut [u64; 4]) {
+pub const fn short_mix(h: &mut [u64; 4]) {
     h[2] = rot64(h[2], 50);
     h[2] = h[2].wrapping_add(h[3]);
     h[0] ^= h[2];
@@ -225,6 +256,32 @@ const fn short_mix(h: &mut [u64; 4]) {
     h[3] ^= h[1];
 }
 
+/// Rotation amounts of `short_mix`, in order.
+const SHORT_MIX_ROTATIONS: [u32; 12] = [50, 52, 30, 41, 54, 48, 38, 37, 62, 34, 5, 36];
+
+/// The inverse of `short_mix`.
+///
+/// `short_mix` is twelve steps of
+///
+/// ```text
+/// h[k] = rot64(h[k], r); h[k] += h[k+1]; h[k+2] ^= h[k];
+/// ```
+///
+/// with `k` running 2, 3, 0, 1, 2, ... and indices taken modulo 4. This
+/// runs them backwards.
+pub const fn short_unmix(h: &mut [u64; 4]) {
+    let mut j = SHORT_MIX_ROTATIONS.len();
+    while j > 0 {
+        j -= 1;
+        let k = (j + 2) % 4;
+        let k1 = (k + 1) % 4;
+        let k2 = (k + 2) % 4;
+        h[k2] ^= h[k];
+        h[k] = h[k].wrapping_sub(h[k1]);
+        h[k] = h[k].rotate_right(SHORT_MIX_ROTATIONS[j]);
+    }
+}
+
 /// > Mix all 4 inputs together so that h0, h1 are a hash of them all.
 /// >
 /// > For two inputs differing in just the input bits
@@ -235,7 +292,6 @@ const fn short_mix(h: &mut [u64; 4]) {
 /// > with probability 50 +- .3% (it is probably better than that)
 /// > For every pair of input bits,
 /// > with probability 50 +- .75% (the worst case is approximately that)
-
 #[inline(always)]
 const fn short_end(h: &mut [u64; 4]) {
     h[3] ^= h[2];
@@ -273,109 +329,98 @@ const fn short_end(h: &mut [u64; 4]) {
     h[1] = h[1].wrapping_add(h[0]);
 }
 
+/// The `SC_NUM_VARS` little-endian words at `offset` in `bytes`.
+#[inline(always)]
+const fn load_block(bytes: &[u8], offset: usize) -> [u64; SC_NUM_VARS] {
+    let mut block = [0u64; SC_NUM_VARS];
+    let mut i = 0;
+    while i < SC_NUM_VARS {
+        block[i] = load_int_le!(bytes, offset + 8 * i, u64);
+        i += 1;
+    }
+    block
+}
+
 /// > Short is used for messages under 192 bytes in length. Short
 /// > has a low startup cost, the normal mode is good for long
 /// > keys, the cost crossover is at about 192 bytes. The two modes
 /// > were held to the same quality bar.
-const fn short(message: &[u8], length: usize, hash1: &mut u64, hash2: &mut u64) {
-    debug_assert!(length <= SC_BUF_SIZE);
+const fn short(message: &[u8], hash1: &mut u64, hash2: &mut u64) {
+    let length = message.len();
+    debug_assert!(length < SC_BUF_SIZE);
     let mut h: [u64; 4] = [*hash1, *hash2, SC_CONST, SC_CONST];
-    let mut bytes_cursor = const_slice_window(message, 0, message.len());
+    let mut remainder = length % 32;
+    let mut p = 0;
 
-    while bytes_cursor.len() >= 4 * mem::size_of::<u64>() {
-        let mut buf = [0u64; 4 * mem::size_of::<u64>()];
-        let words: &[u64] = if (mem::align_of_val(bytes_cursor) & 3) == 0 {
-            unsafe {
-                mem::transmute::<&[u8], &[u64]>(const_slice_window(
-                    bytes_cursor,
-                    0,
-                    4 * mem::size_of::<u64>(),
-                ))
-            }
-        } else {
-            unsafe {
-                ptr::copy_nonoverlapping(
-                    bytes_cursor.as_ptr(),
-                    &mut buf as *mut u64 as *mut u8,
-                    4 * mem::size_of::<u64>(),
-                );
-            }
-            &buf
-        };
-        h[2] = h[2].wrapping_add(words[0]);
-        h[3] = h[3].wrapping_add(words[1]);
-        short_mix(&mut h);
-        h[0] = h[0].wrapping_add(words[2]);
-        h[1] = h[1].wrapping_add(words[3]);
-        bytes_cursor = const_slice_window(
-            bytes_cursor,
-            4 * mem::size_of::<u64>(),
-            bytes_cursor.len() - 4 * mem::size_of::<u64>(),
-        );
+    if length > 15 {
+        // handle all complete sets of 32 bytes
+        while p + 32 <= length {
+            h[2] = h[2].wrapping_add(load_int_le!(message, p, u64));
+            h[3] = h[3].wrapping_add(load_int_le!(message, p + 8, u64));
+            short_mix(&mut h);
+            h[0] = h[0].wrapping_add(load_int_le!(message, p + 16, u64));
+            h[1] = h[1].wrapping_add(load_int_le!(message, p + 24, u64));
+            p += 32;
+        }
+
+        // handle the case of 16+ remaining bytes
+        if remainder >= 16 {
+            h[2] = h[2].wrapping_add(load_int_le!(message, p, u64));
+            h[3] = h[3].wrapping_add(load_int_le!(message, p + 8, u64));
+            short_mix(&mut h);
+            p += 16;
+            remainder -= 16;
+        }
     }
 
-    if bytes_cursor.len() >= 2 * mem::size_of::<u64>() {
-        let mut buf = [0u64; 2 * mem::size_of::<u64>()];
-        let words: &[u64] = if (mem::align_of_val(message) & 3) == 0 {
-            unsafe { mem::transmute::<&[u8], &[u64]>(message) }
-        } else {
-            unsafe {
-                ptr::copy_nonoverlapping(
-                    bytes_cursor.as_ptr(),
-                    &mut buf as *mut u64 as *mut u8,
-                    2 * mem::size_of::<u64>(),
-                );
-            }
-            &buf
-        };
-        h[2] = h[2].wrapping_add(words[0]);
-        h[3] = h[3].wrapping_add(words[1]);
-        short_mix(&mut h);
-    } else if !bytes_cursor.is_empty() {
-        h[3] = h[3].wrapping_add(length as u64).wrapping_shl(56);
-        if bytes_cursor.len() >= 12 {
-            if bytes_cursor.len() > 14 {
-                h[3] = h[3].wrapping_add(bytes_cursor[14] as u64).wrapping_shl(48);
-            }
-            if bytes_cursor.len() > 13 {
-                h[3] = h[3].wrapping_add(bytes_cursor[13] as u64).wrapping_shl(40);
-            }
-            if bytes_cursor.len() > 12 {
-                h[3] = h[3].wrapping_add(bytes_cursor[12] as u64).wrapping_shl(32);
-            }
-            h[2] = h[2].wrapping_add(load_int_le!(bytes_cursor, 0, u64));
-            h[3] = h[3].wrapping_add(load_int_le!(bytes_cursor, 8, u32) as u64);
-        } else if bytes_cursor.len() >= 8 {
-            if bytes_cursor.len() > 10 {
-                h[3] = h[3].wrapping_add(bytes_cursor[10] as u64).wrapping_shl(16);
-            }
-            if bytes_cursor.len() > 9 {
-                h[3] = h[3].wrapping_add(bytes_cursor[9] as u64).wrapping_shl(8);
-            }
-            if bytes_cursor.len() > 8 {
-                h[3] = h[3].wrapping_add(bytes_cursor[8] as u64);
-            }
-            h[2] = h[2].wrapping_add(load_int_le!(bytes_cursor, 0, u64));
-        } else if bytes_cursor.len() >= 4 {
-            if bytes_cursor.len() > 6 {
-                h[2] = h[2].wrapping_add(bytes_cursor[6] as u64).wrapping_shl(48);
-            }
-            if bytes_cursor.len() > 5 {
-                h[2] = h[2].wrapping_add(bytes_cursor[5] as u64).wrapping_shl(40);
-            }
-            if bytes_cursor.len() > 4 {
-                h[2] = h[2].wrapping_add(bytes_cursor[4] as u64).wrapping_shl(32);
-            }
-            h[2] = h[2].wrapping_add(load_int_le!(bytes_cursor, 0, u32) as u64);
-        } else {
-            if bytes_cursor.len() > 2 {
-                h[2] = h[2].wrapping_add(bytes_cursor[2] as u64).wrapping_shl(16);
-            }
-            if bytes_cursor.len() > 1 {
-                h[2] = h[2].wrapping_add(bytes_cursor[1] as u64).wrapping_shl(8);
-            }
-            h[2] = h[2].wrapping_add(bytes_cursor[0] as u64);
+    // handle the last 0..15 bytes, and its length
+    let tail = const_slice_window(message, p, remainder);
+    h[3] = h[3].wrapping_add((length as u64) << 56);
+    if remainder >= 12 {
+        if remainder > 14 {
+            h[3] = h[3].wrapping_add((tail[14] as u64) << 48);
+        }
+        if remainder > 13 {
+            h[3] = h[3].wrapping_add((tail[13] as u64) << 40);
+        }
+        if remainder > 12 {
+            h[3] = h[3].wrapping_add((tail[12] as u64) << 32);
+        }
+        h[3] = h[3].wrapping_add(load_int_le!(tail, 8, u32) as u64);
+        h[2] = h[2].wrapping_add(load_int_le!(tail, 0, u64));
+    } else if remainder >= 8 {
+        if remainder > 10 {
+            h[3] = h[3].wrapping_add((tail[10] as u64) << 16);
+        }
+        if remainder > 9 {
+            h[3] = h[3].wrapping_add((tail[9] as u64) << 8);
+        }
+        if remainder > 8 {
+            h[3] = h[3].wrapping_add(tail[8] as u64);
+        }
+        h[2] = h[2].wrapping_add(load_int_le!(tail, 0, u64));
+    } else if remainder >= 4 {
+        if remainder > 6 {
+            h[2] = h[2].wrapping_add((tail[6] as u64) << 48);
+        }
+        if remainder > 5 {
+            h[2] = h[2].wrapping_add((tail[5] as u64) << 40);
+        }
+        if remainder > 4 {
+            h[2] = h[2].wrapping_add((tail[4] as u64) << 32);
         }
+        h[2] = h[2].wrapping_add(load_int_le!(tail, 0, u32) as u64);
+    } else if remainder > 0 {
+        if remainder > 2 {
+            h[2] = h[2].wrapping_add((tail[2] as u64) << 16);
+        }
+        if remainder > 1 {
+            h[2] = h[2].wrapping_add((tail[1] as u64) << 8);
+        }
+        h[2] = h[2].wrapping_add(tail[0] as u64);
+    } else {
+        h[2] = h[2].wrapping_add(SC_CONST);
+        h[3] = h[3].wrapping_add(SC_CONST);
     }
 
     short_end(&mut h);
@@ -404,61 +449,66 @@ pub struct SpookyHasher {
 }
 
 impl SpookyHasher {
+    /// The same as `new(0, 0)`.
     pub const fn default() -> SpookyHasher {
+        Self::new(0, 0)
+    }
+
+    pub const fn new(seed1: u64, seed2: u64) -> SpookyHasher {
         SpookyHasher {
             m_data: [0; SC_BUF_SIZE],
-            m_state: [0; SC_NUM_VARS],
+            m_state: [
+                seed1, seed2, SC_CONST, seed1, seed2, SC_CONST, seed1, seed2, SC_CONST, seed1,
+                seed2, SC_CONST,
+            ],
             m_length: 0,
             m_remainder: 0,
         }
     }
-    pub const fn new(seed1: u64, seed2: u64) -> SpookyHasher {
-        let mut sh = Self::default();
-        sh.m_state[0] = seed1;
-        sh.m_state[3] = seed1;
-        sh.m_state[6] = seed1;
-        sh.m_state[9] = seed1;
-        sh.m_state[1] = seed2;
-        sh.m_state[4] = seed2;
-        sh.m_state[7] = seed2;
-        sh.m_state[10] = seed2;
-        sh.m_state[2] = SC_CONST;
-        sh.m_state[5] = SC_CONST;
-        sh.m_state[8] = SC_CONST;
-        sh.m_state[11] = SC_CONST;
-        sh
+
+    /// Both halves of the reference 128-bit seed are `seed`, as in the
+    /// reference `SpookyHash::Hash64(message, length, seed)`.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> SpookyHasher {
+        SpookyHasher::new(seed, seed)
+    }
+
+    /// The low 64 bits of the seed are `seed1` and the high 64 bits are
+    /// `seed2`.
+    #[inline(always)]
+    pub const fn with_seed128(seed: u128) -> SpookyHasher {
+        SpookyHasher::new(seed as u64, (seed >> 64) as u64)
     }
 
     pub const fn finish128(&self) -> (u64, u64) {
         if self.m_length < SC_BUF_SIZE {
             let mut hash1 = self.m_state[0];
             let mut hash2 = self.m_state[1];
-            short(&self.m_data, self.m_length, &mut hash1, &mut hash2);
+            short(
+                const_slice_window(&self.m_data, 0, self.m_length),
+                &mut hash1,
+                &mut hash2,
+            );
             return (hash1, hash2);
         }
         let mut state = self.m_state;
         let mut remainder = self.m_remainder;
         let mut processed = 0;
         if self.m_remainder >= SC_BLOCK_SIZE {
-            let data = unsafe { mem::transmute::<&[u8], &[u64]>(&self.m_data) };
-            mix(data, &mut state);
+            mix(&load_block(&self.m_data, 0), &mut state);
             processed = SC_BLOCK_SIZE;
             remainder -= SC_BLOCK_SIZE;
         }
-        let mut data = [0u64; SC_NUM_VARS];
+        let mut last = [0u8; SC_BLOCK_SIZE];
         unsafe {
             ptr::copy_nonoverlapping::<u8>(
                 (&self.m_data as *const u8).add(processed),
-                data.as_mut_ptr() as *mut u8,
+                last.as_mut_ptr(),
                 remainder,
             );
-            ptr::write_bytes(
-                (data.as_mut_ptr() as *mut u8).add(SC_BLOCK_SIZE - 1),
-                remainder as u8,
-                1,
-            );
         }
-        end(&data, &mut state);
+        last[SC_BLOCK_SIZE - 1] = remainder as u8;
+        end(&load_block(&last, 0), &mut state);
         (state[0], state[1])
     }
 
@@ -495,32 +545,15 @@ impl SpookyHasher {
                     processed,
                 );
             }
-            let data: &[u64] = unsafe { mem::transmute::<&[u8], &[u64]>(&self.m_data) };
-            mix(data, &mut self.m_state);
-            mix(
-                const_slice_window(data, SC_NUM_VARS, data.len() - SC_NUM_VARS),
-                &mut self.m_state,
-            );
+            mix(&load_block(&self.m_data, 0), &mut self.m_state);
+            mix(&load_block(&self.m_data, SC_BLOCK_SIZE), &mut self.m_state);
             self.m_remainder = 0;
         }
         // process the rest of the bytes
         let mut bytes_cursor = const_slice_window(bytes, processed, bytes.len() - processed);
         while bytes_cursor.len() >= SC_BLOCK_SIZE {
             // handle whole blocks of SC_BLOCK_SIZE bytes
-            if (mem::align_of_val(bytes_cursor) & 7) == 0 {
-                let data: &[u64] = unsafe { mem::transmute::<&[u8], &[u64]>(bytes_cursor) };
-                mix(data, &mut self.m_state);
-            } else {
-                unsafe {
-                    ptr::copy_nonoverlapping(
-                        bytes_cursor.as_ptr(),
-                        self.m_data.as_mut_ptr(),
-                        SC_BLOCK_SIZE,
-                    );
-                }
-                let data: &[u64] = unsafe { mem::transmute::<&[u8], &[u64]>(&self.m_data) };
-                mix(data, &mut self.m_state);
-            }
+            mix(&load_block(bytes_cursor, 0), &mut self.m_state);
             bytes_cursor = const_slice_window(
                 bytes_cursor,
                 SC_BLOCK_SIZE,
@@ -542,6 +575,20 @@ impl SpookyHasher {
 }
 
 duplicate_const_traits!(SpookyHasher);
+
+impl crate::SeedableHasher for SpookyHasher {
+    #[inline(always)]
+    fn with_seed(seed: u64) -> SpookyHasher {
+        SpookyHasher::with_seed(seed)
+    }
+
+    #[inline(always)]
+    fn with_seed128(seed: u128) -> SpookyHasher {
+        SpookyHasher::with_seed128(seed)
+    }
+}
+
+seeded_const!(SpookyHasher);
 hasher_to_fcn!(
     /// Provide access to Lookup3Hasher in a single call.
     spooky,
@@ -552,14 +599,48 @@ hasher_to_fcn!(
 mod spookyhash_test {
     use super::*;
 
+    #[test]
+    fn seeded() {
+        let mut h = SpookyHasher::with_seed(0);
+        h.write(b"abcdefg");
+        assert_eq!(h.finish(), spooky(b"abcdefg"));
+        assert_eq!(
+            SpookyHasher::with_seed128(1 | 2 << 64),
+            SpookyHasher::new(1, 2)
+        );
+        let h: SpookyHasher = crate::SeedableHasher::with_seed128(3 | 3 << 64);
+        assert_eq!(h, SpookyHasher::with_seed(3));
+    }
+
     #[test]
     fn basic() {
-        assert_eq!(spooky(b""), 13905049616862401802);
-        assert_eq!(spooky(b"a"), 16932945748884078726);
-        assert_eq!(spooky(b"b"), 5781063613870495197);
-        assert_eq!(spooky(b"ab"), 13849109452443161137);
-        assert_eq!(spooky(b"abcd"), 4142038200967391753);
-        assert_eq!(spooky(b"abcdefg"), 2761526316938866980);
-        assert_eq!(spooky(b"abcdefghijklmnopqrstuvwxyz"), 16192181224158463141);
+        assert_eq!(spooky(b""), 2533000996631939353);
+        assert_eq!(spooky(b"a"), 1878143507080399293);
+        assert_eq!(spooky(b"b"), 13471851842775403689);
+        assert_eq!(spooky(b"ab"), 18004184790028912911);
+        assert_eq!(spooky(b"abcd"), 6660178300034425268);
+        assert_eq!(spooky(b"abcdefg"), 16227871758974952830);
+        assert_eq!(spooky(b"abcdefghijklmnopqrstuvwxyz"), 12198529059602199121);
+    }
+
+    #[test]
+    fn unmix() {
+        let data: [u64; SC_NUM_VARS] = core::array::from_fn(|i| (i as u64) * 0x0101010101010101);
+        let before: [u64; SC_NUM_VARS] = core::array::from_fn(|i| SC_CONST.rotate_left(i as u32));
+        let mut state = before;
+        mix(&data, &mut state);
+        assert_ne!(state, before);
+        super::unmix(&data, &mut state);
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn short_unmix() {
+        let before = [1u64, 2, SC_CONST, SC_CONST];
+        let mut h = before;
+        short_mix(&mut h);
+        assert_ne!(h, before);
+        super::short_unmix(&mut h);
+        assert_eq!(h, before);
     }
 }
@@ -0,0 +1,336 @@
+//! Keys to hash: seeded, deterministic keyset generators and the bundled
+//! word lists.
+//!
+//! Every generator takes a seed, or needs none, and produces the same keys
+//! on every run and every platform, so a test that fails on one machine
+//! fails on all of them. The random keysets are drawn from
+//! [`SplitMix64`], and each keyset's keys are distinct, so any collision
+//! among their hashes is the hash's doing.
+//!
+//! - [`uniform`] and [`alphanumeric`]: random bytes, and random ASCII
+//!   letters and digits.
+//! - [`identifiers`]: sequential identifiers, `a000000`, `a000001`...
+//! - [`sparse`]: keys of zero bytes with a few bits set. ([`crate::frog`]
+//!   has all of them, in order.)
+//! - [`cyclic`]: a short block repeated.
+//! - [`uuids`], [`ipv4`], [`ipv6`] and [`urls`]: text in the shape of
+//!   UUIDs, IP addresses and URLs.
+//! - [`integers`]: a range of integers, as little-endian bytes.
+//! - [`words`] and [`anadict_keys`]: the lines of `data/words.txt` and the
+//!   sorted-letter keys of `data/anadict.txt`, compiled into the crate.
+
+use std::collections::HashSet;
+
+use crate::mixers::SplitMix64;
+
+/// `data/words.txt`: 99,171 English words, one per line.
+pub static WORDS: &[u8] = include_bytes!("../data/words.txt");
+
+/// `data/anadict.txt`, the anagram dictionary: each line is a word's
+/// letters in sorted order, followed by the words made of those letters,
+/// separated by spaces.
+pub static ANADICT: &[u8] = include_bytes!("../data/anadict.txt");
+
+/// The words of [`WORDS`].
+pub fn words() -> Vec<&'static [u8]> {
+    lines(WORDS).collect()
+}
+
+/// The sorted-letter keys of [`ANADICT`], the first field of each line.
+pub fn anadict_keys() -> Vec<&'static [u8]> {
+    lines(ANADICT)
+        .map(|line| line.split(|&b| b == b' ').next().unwrap_or(line))
+        .collect()
+}
+
+fn lines(text: &'static [u8]) -> impl Iterator<Item = &'static [u8]> {
+    text.split(|&b| b == b'\n').filter(|line| !line.is_empty())
+}
+
+// ====================================
+// Random keys
+
+/// `n` distinct keys from `generate`, in the order generated.
+fn distinct<F: FnMut(&mut SplitMix64) -> Vec<u8>>(
+    n: usize,
+    seed: u64,
+    mut generate: F,
+) -> Vec<Vec<u8>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut seen = HashSet::with_capacity(n);
+    let mut keys = Vec::with_capacity(n);
+    while keys.len() < n {
+        let key = generate(&mut rng);
+        if seen.insert(key.clone()) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Whether there are at least `n` keys of `len` symbols from an alphabet
+/// of `size`.
+fn enough(n: usize, size: u128, len: usize) -> bool {
+    let mut keys = 1u128;
+    for _ in 0..len {
+        keys = keys.saturating_mul(size);
+    }
+    n as u128 <= keys
+}
+
+/// `n` distinct keys of `len` uniformly random bytes.
+pub fn uniform(n: usize, len: usize, seed: u64) -> Vec<Vec<u8>> {
+    assert!(enough(n, 256, len), "fewer than n keys of len bytes");
+    distinct(n, seed, |rng| {
+        let mut key = Vec::with_capacity(len + 8);
+        while key.len() < len {
+            key.extend_from_slice(&rng.next().to_le_bytes());
+        }
+        key.truncate(len);
+        key
+    })
+}
+
+const ALPHANUMERIC: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// `n` distinct keys of `len` random ASCII letters and digits.
+pub fn alphanumeric(n: usize, len: usize, seed: u64) -> Vec<Vec<u8>> {
+    assert!(enough(n, 62, len), "fewer than n keys of len characters");
+    distinct(n, seed, |rng| {
+        (0..len)
+            .map(|_| ALPHANUMERIC[(rng.next() % 62) as usize])
+            .collect()
+    })
+}
+
+/// `n` distinct keys of `len` bytes that are zero except for `set` random
+/// bits.
+pub fn sparse(n: usize, len: usize, set: usize, seed: u64) -> Vec<Vec<u8>> {
+    let bits = len * 8;
+    assert!(set <= bits, "more bits set than in the key");
+    let mut binomial = 1u128;
+    for k in 0..set {
+        binomial = binomial.saturating_mul((bits - k) as u128) / (k as u128 + 1);
+    }
+    assert!(n as u128 <= binomial, "fewer than n keys with set bits set");
+    distinct(n, seed, |rng| {
+        let mut key = vec![0u8; len];
+        let mut count = 0;
+        while count < set {
+            let bit = (rng.next() % bits as u64) as usize;
+            if key[bit / 8] & (1 << (bit % 8)) == 0 {
+                key[bit / 8] |= 1 << (bit % 8);
+                count += 1;
+            }
+        }
+        key
+    })
+}
+
+/// `n` keys of a random block of `cycle` bytes repeated `repeats` times.
+/// The first 4 bytes of each block are the key's index, so the keys are
+/// distinct.
+pub fn cyclic(n: usize, cycle: usize, repeats: usize, seed: u64) -> Vec<Vec<u8>> {
+    assert!(cycle >= 4, "cycle shorter than the index");
+    assert!(n as u64 <= 1 << 32, "more keys than indexes");
+    let mut rng = SplitMix64::new(seed);
+    (0..n as u64)
+        .map(|i| {
+            let mut block = Vec::with_capacity(cycle + 8);
+            block.extend_from_slice(&(i as u32).to_le_bytes());
+            while block.len() < cycle {
+                block.extend_from_slice(&rng.next().to_le_bytes());
+            }
+            block.truncate(cycle);
+            block.repeat(repeats)
+        })
+        .collect()
+}
+
+/// `n` distinct random version 4 UUIDs, as lowercase hex with hyphens:
+/// `xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx`, y being 8, 9, a or b.
+pub fn uuids(n: usize, seed: u64) -> Vec<Vec<u8>> {
+    distinct(n, seed, |rng| {
+        let hi = rng.next();
+        let lo = rng.next();
+        format!(
+            "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+            hi >> 32,
+            (hi >> 16) & 0xffff,
+            hi & 0xfff,
+            0x8000 | ((lo >> 48) & 0x3fff),
+            lo & 0xffff_ffff_ffff
+        )
+        .into_bytes()
+    })
+}
+
+/// `n` distinct random IPv4 addresses in the private network 10.0.0.0/8,
+/// dotted-decimal.
+pub fn ipv4(n: usize, seed: u64) -> Vec<Vec<u8>> {
+    assert!(n <= 1 << 24, "more keys than addresses");
+    distinct(n, seed, |rng| {
+        let [a, b, c, ..] = rng.next().to_le_bytes();
+        format!("10.{}.{}.{}", a, b, c).into_bytes()
+    })
+}
+
+/// `n` distinct random IPv6 addresses in the documentation prefix
+/// 2001:db8::/32, as eight groups of hex digits without leading zeros.
+pub fn ipv6(n: usize, seed: u64) -> Vec<Vec<u8>> {
+    distinct(n, seed, |rng| {
+        let hi = rng.next();
+        let lo = rng.next();
+        let mut address = String::from("2001:db8");
+        for group in [hi >> 48, hi >> 32, hi >> 16, lo >> 48, lo >> 32, lo >> 16] {
+            address.push_str(&format!(":{:x}", group & 0xffff));
+        }
+        address.into_bytes()
+    })
+}
+
+const HOSTS: [&str; 4] = [
+    "example.com",
+    "www.example.org",
+    "api.example.net",
+    "static.example.com",
+];
+
+/// `n` URL-like keys: one of a few hosts, a path of one to three random
+/// [`words`], and the key's index as a query, so the keys are distinct:
+/// `https://www.example.org/aardvark/zymurgy?id=42`.
+pub fn urls(n: usize, seed: u64) -> Vec<Vec<u8>> {
+    let words = words();
+    let mut rng = SplitMix64::new(seed);
+    (0..n)
+        .map(|i| {
+            let mut url = format!("https://{}", HOSTS[(rng.next() % 4) as usize]).into_bytes();
+            for _ in 0..=rng.next() % 3 {
+                url.push(b'/');
+                url.extend_from_slice(words[(rng.next() % words.len() as u64) as usize]);
+            }
+            url.extend_from_slice(format!("?id={}", i).as_bytes());
+            url
+        })
+        .collect()
+}
+
+// ====================================
+// Sequential keys
+
+/// `n` identifiers of the form `a000042`: "a" and the index, with at
+/// least `width` digits.
+pub fn identifiers(n: usize, width: usize) -> Vec<Vec<u8>> {
+    (0..n)
+        .map(|i| format!("a{:0width$}", i, width = width).into_bytes())
+        .collect()
+}
+
+/// The integers in `range`, as their low `width` little-endian bytes.
+pub fn integers(range: core::ops::Range<u64>, width: usize) -> Vec<Vec<u8>> {
+    assert!((1..=8).contains(&width), "width not 1 to 8 bytes");
+    assert!(
+        width == 8 || range.end <= 1 << (8 * width),
+        "integers wider than width"
+    );
+    range.map(|i| i.to_le_bytes()[..width].to_vec()).collect()
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod keysets_tests {
+    use super::*;
+
+    fn is_distinct<K: AsRef<[u8]>>(keys: &[K]) -> bool {
+        let set: HashSet<&[u8]> = keys.iter().map(|k| k.as_ref()).collect();
+        set.len() == keys.len()
+    }
+
+    #[test]
+    fn corpora() {
+        let words = words();
+        assert_eq!(words.len(), 99171);
+        assert!(is_distinct(&words));
+        assert!(words.iter().all(|w| !w.contains(&b'\n')));
+        let keys = anadict_keys();
+        assert_eq!(keys.len(), 58505);
+        assert_eq!(keys[0], b"aaaaabbcdrr");
+        assert!(is_distinct(&keys));
+    }
+
+    #[test]
+    fn random() {
+        assert_eq!(uniform(100, 6, 1), uniform(100, 6, 1));
+        assert_ne!(uniform(100, 6, 1), uniform(100, 6, 2));
+        // every one-byte key
+        let mut bytes = uniform(256, 1, 0);
+        assert!(is_distinct(&bytes));
+        bytes.sort();
+        assert_eq!(bytes, integers(0..256, 1));
+
+        let keys = alphanumeric(1000, 6, 1);
+        assert!(is_distinct(&keys));
+        assert!(keys.iter().flatten().all(|b| b.is_ascii_alphanumeric()));
+
+        let keys = sparse(1000, 16, 3, 1);
+        assert!(is_distinct(&keys));
+        assert!(
+            keys.iter()
+                .all(|k| k.len() == 16 && k.iter().map(|b| b.count_ones()).sum::<u32>() == 3)
+        );
+        assert_eq!(sparse(128, 16, 1, 1).len(), 128);
+
+        let keys = cyclic(100, 5, 8, 1);
+        assert!(is_distinct(&keys));
+        assert_eq!(keys[7].len(), 40);
+        assert_eq!(keys[7][..5], keys[7][35..]);
+        assert_eq!(keys[7][..4], [7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn text() {
+        let keys = uuids(1000, 1);
+        assert!(is_distinct(&keys));
+        for key in &keys {
+            let uuid = core::str::from_utf8(key).unwrap();
+            let groups: Vec<&str> = uuid.split('-').collect();
+            let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+            assert_eq!(lengths, [8, 4, 4, 4, 12]);
+            assert!(groups[2].starts_with('4'));
+            assert!("89ab".contains(&groups[3][..1]));
+        }
+
+        let keys = ipv4(1000, 1);
+        assert!(is_distinct(&keys));
+        for key in &keys {
+            let address = core::str::from_utf8(key).unwrap();
+            let address: std::net::Ipv4Addr = address.parse().unwrap();
+            assert_eq!(address.octets()[0], 10);
+        }
+
+        let keys = ipv6(1000, 1);
+        assert!(is_distinct(&keys));
+        for key in &keys {
+            let address = core::str::from_utf8(key).unwrap();
+            let address: std::net::Ipv6Addr = address.parse().unwrap();
+            assert_eq!(address.segments()[..2], [0x2001, 0xdb8]);
+        }
+
+        let keys = urls(1000, 1);
+        assert!(is_distinct(&keys));
+        assert!(keys[42].starts_with(b"https://") && keys[42].ends_with(b"?id=42"));
+        assert_eq!(urls(10, 3), urls(10, 3));
+    }
+
+    #[test]
+    fn sequential() {
+        let keys = identifiers(1001, 3);
+        assert_eq!(keys[0], b"a000");
+        assert_eq!(keys[42], b"a042");
+        assert_eq!(keys[1000], b"a1000");
+        let keys = integers(254..258, 2);
+        assert_eq!(keys, [[254, 0], [255, 0], [0, 1], [1, 1]]);
+        assert_eq!(integers(0..3, 8)[2], 2u64.to_le_bytes());
+    }
+}
@@ -5,7 +5,7 @@
 //! HashMap and HashSet.
 //!
 //! Additionally, there are benchmarks of the hash functions and a
-//! couple of statistical tests for hash quality.
+//! module of statistical tests for hash quality.
 //!
 //! # Disclaimer
 //!
@@ -117,6 +117,33 @@
 //! A more complicated example is the anagrams-hashmap.rs example program included with this
 //! module.
 //!
+//! ## Random seeds
+//!
+//! Every hash algorithm in this crate implements `SeedableHasher`, with a `with_seed(u64)`
+//! constructor that is also available as a `const fn`, as are the aliases like `DJB2Finalized`. The
+//! generic wrappers `Finalized`, `Portable` and `Hardened` implement it too, but not as a `const fn`.
+//!
+//! BuildHasherDefault gives every map the same Hasher state. With the `std` feature, the
+//! `random` module has a RandomState-like builder for each Hasher, which seeds it from the
+//! operating system's randomness and differs from map to map.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")] {
+//! use std::collections::HashMap;
+//!
+//! use const_hashers::random::RandomLookup3State;
+//!
+//! let mut map: HashMap<i32, i32, RandomLookup3State> = HashMap::default();
+//!
+//! map.insert(1, 2);
+//! assert_eq!(map.get(&1), Some(&2));
+//! # }
+//! ```
+//!
+//! `Seeded<H, SEED>` puts the seed in the type instead, and `BuildSeeded<H>` uses `BUILD_SEED`,
+//! which is chosen at random each time the crate is built. Set `CONST_HASHERS_BUILD_SEED` to a
+//! u64 when building to fix it, for reproducible builds.
+//!
 //! # About this crate
 //!
 //! This collection of Hashers is based on:
@@ -126,6 +153,40 @@
 //! - http://burtleburtle.net/bob/hash/spooky.html Jenkin's SpookyHash. (jenkins::spooky_hash)
 //! - Rust's builtin DefaultHasher (SIP 1-3?) (default)
 //! - http://www.isthe.com/chongo/tech/comp/fnv/ The Fowler/Noll/Vo hash algorithm. (fnv)
+//! - Integer mixers: SplitMix64, MurmurHash3's fmix, Stafford's Mix01-14, Wang's and Jenkins'
+//!   integer hashes, and friends, with their inverses. `Finalized` runs a Hasher's result through
+//!   one, and `DJB2Finalized` and friends spread the narrow Hashers over 64 bits for std's
+//!   `HashMap`. (mixers)
+//! - Peter K. Pearson's 1990 CACM hash, with tables generated at compile time. (pearson)
+//! - Simple and twisted tabulation hashing, after Pătraşcu and Thorup. (tabulation)
+//! - Keyed universal families with collision bounds: multiply-shift, polynomial hashing mod
+//!   2^61 - 1, and Polymur-hash. (universal)
+//! - Rolling hashes over a sliding window: Rabin-Karp, Buzhash and Gear, with substring
+//!   search. (rolling)
+//! - FastCDC content-defined chunking, with SpookyHash chunk fingerprints. (cdc)
+//! - k-ary Merkle trees over 128-bit hashes, with tree diffing. (merkle)
+//! - Zobrist hashing for game and state-space search, with 64- and 128-bit keys. (zobrist)
+//! - RandomState-style builders that seed each Hasher from the operating system's randomness.
+//!   (random, with the std feature)
+//! - Hardened<H>, which keys any Hasher with a secret prefix and a SipHash-1-3 final mix
+//!   against hash flooding. (hardened)
+//! - Portable<H>, which writes integers to any Hasher as fixed-width little-endian bytes, so
+//!   hashes agree between 32- and 64-bit targets. (portable)
+//! - Conformance checks for any Hasher: split writes, clones, seeds, empty writes and const
+//!   evaluation. (conformance)
+//! - An SMHasher-style test battery, with pass or fail and the measured bias for each test, for any
+//!   Hasher. (battery)
+//! - Bob Jenkins' frog.c test: sparse "evil" keys and their first collision, against the birthday
+//!   expectation. (frog)
+//! - A hash table simulator reporting probe lengths for linear probing, Robin Hood and
+//!   SwissTable layouts at several load factors. (probe)
+//! - Statistical tests of hash quality: chi-squared, Kolmogorov-Smirnov and Anderson-Darling, with
+//!   p-values, for any `fn(&[u8]) -> u64`. (quality)
+//! - Avalanche matrix and hash bitmap pictures, as PPM, PGM and CSV, for any Hasher. (visual)
+//! - Collision counts at every truncation width from 16 to 64 bits, against the birthday bound,
+//!   streaming millions of keys. (collisions)
+//! - Seeded, deterministic keysets: uniform, alphanumeric, sequential, sparse, cyclic, UUID, IP
+//!   address, URL and integer keys, and the bundled word lists. (keysets)
 //! - Two "null" hashers: NullHasher returns 0 for all inputs and PassThroughHasher returns the
 //!   last 8 bytes of the data.
 //!
@@ -133,42 +194,80 @@
 //! module has a benchmarking module for comparing the Hashers and some example programs using
 //! statistical tests to prod the various Hashers.
 //!
+//! The default features are the hash algorithms. The analysis modules (conformance, quality,
+//! battery, frog, probe, visual, collisions and keysets) must be enabled by name, and so must the
+//! features of the example programs below, for instance
+//! `cargo run --release --features quality,keysets --example quality`. The benchmarks over the
+//! word list need the keysets feature.
+//!
 //! # Example programs
 //!
-//! ## chi2
+//! ## quality
 //!
-//! > The chi-squared test is used to determine whether there is a significant difference between
-//! > the expected frequencies and the observed frequencies in one or more categories. --
-//! > [Chi-squared test](https://en.wikipedia.org/wiki/Chi-squared_test)
+//! This program runs the tests of the quality module on each Hasher, for each of a number of
+//! samples:
 //!
-//! This program attempts to compute the hash values for one of a number of data sets, then
-//! simulates using those values in a 128-bucket hash table (a 2^7 - 1 mask) and tries to determine
-//! if the hash buckets are uniformly distributed. I think. I'm not a statistician and apparently
-//! not much of a programmer any more. Sorry.
+//! - Pearson's [chi-squared test](https://en.wikipedia.org/wiki/Chi-squared_test) of the lower bits
+//!   of the hash values, as buckets of a hash table with an average of at least 8 entries per bucket.
+//! - The [Kolmogorov–Smirnov
+//!   test](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test) and the
+//!   [Anderson-Darling test](https://en.wikipedia.org/wiki/Anderson%E2%80%93Darling_test) of the
+//!   whole hash values against the uniform distribution.
 //!
-//! Anyway, it shows what may be the chi2 test of the lower bits of the hash values for a number of
-//! samples and for each Hasher. Numbers closer to 0 are better, and between 3.0 and -3.0 are
-//! apparently "ok." Maybe.
+//! Each test reports its statistic and p-value, the probability that uniformly distributed hash
+//! values would do at least as badly. A Hasher fails a sample if any p-value is below 0.01, but
+//! expect a good Hasher to fail one test in a hundred anyway. 32-bit Hashers like DJB2 are tested as
+//! 32-bit values.
 //!
 //! The samples are:
+//!
 //! - 1000 uniformly distributed 6-byte binary values.
 //! - 1000 uniformly distributed 6-byte alphanumeric (ASCII) values.
-//! - 1000 generated identifiers of the form 'annnnn'.
+//! - 1000 generated identifiers of the form 'annnnnn'.
 //! - The words from data/words.txt
 //!
-//! ## kolmogorov-smirnov
+//! They come from the keysets module, with fixed seeds, so every run tests the same keys.
+//!
+//! ## battery
+//!
+//! This program runs the SMHasher-style battery of the battery module on each Hasher: avalanche,
+//! bit independence and differential tests, and collision and distribution tests on cyclic, sparse,
+//! two-byte, permutation, combination, window, text and zero keys and on sequential seeds. It prints
+//! pass or fail, the measured bias and the p-value of each test. An optional argument scales the
+//! number of random trials and keys; the default, 1.0, takes a few seconds per Hasher in a release
+//! build.
+//!
+//! ## frog
 //!
-//! > The Kolmogorov–Smirnov statistic quantifies a distance
-//! > between the empirical distribution function of the
-//! > sample and the cumulative distribution function of
-//! > the reference distribution. -- [Kolmogorov–Smirnov
-//! > test](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test).
+//! Bob Jenkins' frog.c test, scaled down to run in seconds: hash 16-byte keys that are zero except
+//! for at most 3 bits, and report the first collision in each 32-bit window of the hash values,
+//! against the birthday expectation of about 82,000 keys. A collision that a random function would
+//! have made that early with probability less than 0.001 is marked FAIL.
 //!
-//! Ok, this one I have a bit more confidence in. It hashes the same samples as the chi2 program,
-//! then attempts to determine how far from uniformly distributed the 64-bit hash values are,
-//! reporting values between 0.0 and 1.0. Lower values are better. 32-bit hashes like DJB2
-//! trivially fail this test, though, although they may be fine for HashMaps with much less than 2^32
-//! entries.
+//! ## visualize
+//!
+//! This program draws pictures of each Hasher, in the directory given as its argument (by default
+//! target/visual): its avalanche matrix for 8-byte keys, white where an output bit flips half the
+//! time when an input bit flips and blue or red where it flips less or more often, as a PPM image and
+//! as CSV; and the bits of the hashes of the keys 0 to 255, as a PGM image and as CSV. LoseLose's
+//! pictures are mostly blue and mostly white; Spooky's are white and noise.
+//!
+//! ## probe
+//!
+//! This program simulates hash tables with linear probing, Robin Hood and SwissTable layouts (the
+//! last being hashbrown's, and so std's HashMap's) at load factors 0.5, 0.75 and 0.875, for each
+//! Hasher and for the same samples as the quality program. It prints the mean and maximum probe
+//! lengths and key comparisons per lookup, next to those for a random function, and warns about
+//! Hashers that do much worse, or whose upper bits never change, like DJB2's.
+//!
+//! ## collisions
+//!
+//! This program counts the collisions among the low bits of each Hasher's hash values, at every
+//! width from 16 to 64 bits, for the words from data/words.txt and a million each of generated
+//! identifiers, UUIDs and URLs. It prints the observed and expected collisions at 24, 32, 40, 48
+//! and 64 bits, and FAIL with the range of widths where a random function would have had as many
+//! with probability less than 0.01. A 32-bit Hasher's collisions at 32 bits are still collisions at
+//! 64, so the 32-bit Hashers fail at the wider widths whenever they collide at all.
 //!
 //! ## anagrams-hashmap
 //!
@@ -240,6 +339,56 @@ macro_rules! duplicate_const_traits {
 
 }
 
+// Implement SeedableHasher using the constant-time with_seed, and give
+// Seeded<$name, SEED> constant-time default, finish and write.
+#[allow(unused_macros)] // unused when no hash algorithm is enabled
+macro_rules! seedable_hasher {
+
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        impl $crate::SeedableHasher for $name {
+            #[inline(always)]
+            fn with_seed(seed: u64) -> $name {
+                $name::with_seed(seed)
+            }
+        }
+
+        seeded_const!($name);
+    };
+
+}
+
+// Constant-time methods for Seeded<$name, SEED>, and $name::with_build_seed.
+#[allow(unused_macros)] // unused when no hash algorithm is enabled
+macro_rules! seeded_const {
+    ($name:ident) => {
+        impl $name {
+            /// Create a Hasher seeded with [`BUILD_SEED`]($crate::BUILD_SEED).
+            #[inline(always)]
+            pub const fn with_build_seed() -> $name {
+                $name::with_seed($crate::BUILD_SEED)
+            }
+        }
+
+        impl<const SEED: u64> $crate::Seeded<$name, SEED> {
+            #[inline(always)]
+            pub const fn default() -> $crate::Seeded<$name, SEED> {
+                $crate::Seeded($name::with_seed(SEED))
+            }
+
+            #[inline(always)]
+            pub const fn finish(&self) -> u64 {
+                self.0.finish()
+            }
+
+            #[inline(always)]
+            pub const fn write(&mut self, bytes: &[u8]) {
+                self.0.write(bytes);
+            }
+        }
+    };
+}
+
 // Given a Hasher, create a single-use hash function.
 macro_rules! hasher_to_fcn {
 
@@ -255,15 +404,212 @@ macro_rules! hasher_to_fcn {
 
 }
 
+// Define $name as $hasher finalized with fmix64, with constant-time default,
+// with_seed, finish and write, and a single-use hash function $fcn.
+#[allow(unused_macros)] // unused without jenkins, oz or fnv
+macro_rules! finalized_hasher {
+
+    ($(#[$attr:meta])* $name:ident, $hasher:ident, $fcn:ident) => {
+        $(#[$attr])*
+        #[cfg(feature = "mixers")]
+        pub type $name = $crate::mixers::Finalized<$hasher, $crate::mixers::Murmur3>;
+
+        #[cfg(feature = "mixers")]
+        impl $crate::mixers::Finalized<$hasher, $crate::mixers::Murmur3> {
+            #[inline(always)]
+            pub const fn default() -> $name {
+                $crate::mixers::Finalized::new($hasher::default())
+            }
+
+            #[inline(always)]
+            pub const fn with_seed(seed: u64) -> $name {
+                $crate::mixers::Finalized::new($hasher::with_seed(seed))
+            }
+
+            #[inline(always)]
+            pub const fn finish(&self) -> u64 {
+                $crate::mixers::fmix64(self.hasher.finish())
+            }
+
+            #[inline(always)]
+            pub const fn write(&mut self, bytes: &[u8]) {
+                self.hasher.write(bytes);
+            }
+        }
+
+        hasher_to_fcn!(
+            #[doc = concat!("Provide access to ", stringify!($name), " in a single call.")]
+            #[cfg(feature = "mixers")]
+            $fcn,
+            $name
+        );
+    };
+
+}
+
+// ====================================
+// Seeding
+
+/// Hashers whose initial state can be chosen with a seed.
+///
+/// Every hash algorithm in this crate also has an inherent `const fn with_seed(seed: u64)`,
+/// which is what this trait calls, so seeded Hashers can be built in constants; so do the
+/// aliases like `DJB2Finalized`. The generic wrappers (`Finalized`, `Portable`, `Hardened`)
+/// implement only the trait.
+///
+/// Each Hasher documents how the seed maps to its initial state. Where the reference
+/// implementation takes a seed (Lookup3, SpookyHash, Poly61), the mapping matches it. Where it
+/// doesn't, the seed is xored into (or replaces) the reference initial state, so that
+/// `with_seed(0)` is the reference algorithm.
+pub trait SeedableHasher: core::hash::Hasher + Sized {
+    /// Create a Hasher from a 64-bit seed.
+    fn with_seed(seed: u64) -> Self;
+
+    /// Create a Hasher from a 128-bit seed. Hashers that can use only 64 bits of seed xor the
+    /// two halves together, so `with_seed128(s as u128)` is `with_seed(s)`.
+    #[inline]
+    fn with_seed128(seed: u128) -> Self {
+        Self::with_seed(seed as u64 ^ (seed >> 64) as u64)
+    }
+}
+
+/// A Hasher whose seed is part of its type.
+///
+/// `Seeded<H, SEED>::default()` is `H::with_seed(SEED)`, so
+/// `BuildHasherDefault<Seeded<H, SEED>>` is a zero-sized, `Default` builder whose Hashers are
+/// seeded, and maps with different `SEED`s have different types and hash differently. For the
+/// Hashers in this crate, `default`, `finish` and `write` are also `const fn`s.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::hash::BuildHasherDefault;
+///
+/// use const_hashers::Seeded;
+/// use const_hashers::jenkins::Lookup3Hasher;
+///
+/// let mut map: HashMap<i32, i32, BuildHasherDefault<Seeded<Lookup3Hasher, 0x1234>>> =
+///     HashMap::default();
+///
+/// map.insert(1, 2);
+/// assert_eq!(map.get(&1), Some(&2));
+/// ```
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Seeded<H, const SEED: u64>(pub H);
+
+impl<H: SeedableHasher, const SEED: u64> Default for Seeded<H, SEED> {
+    #[inline]
+    fn default() -> Seeded<H, SEED> {
+        Seeded(H::with_seed(SEED))
+    }
+}
+
+impl<H: core::hash::Hasher, const SEED: u64> core::hash::Hasher for Seeded<H, SEED> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, i: u8) {
+        self.0.write_u8(i);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, i: u16) {
+        self.0.write_u16(i);
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, i: u32) {
+        self.0.write_u32(i);
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, i: u64) {
+        self.0.write_u64(i);
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, i: u128) {
+        self.0.write_u128(i);
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, i: usize) {
+        self.0.write_usize(i);
+    }
+}
+
+/// A random seed, chosen when this crate is built and fixed for the life of that build.
+///
+/// Hashes computed at compile time with `with_build_seed()` or [`BuildSeeded`] differ from one
+/// build to the next, so they cannot be predicted from the source. The build script only reruns
+/// when the environment variable below changes or the crate is rebuilt from scratch, so
+/// incremental builds keep their seed.
+///
+/// For reproducible builds, set `CONST_HASHERS_BUILD_SEED` to a decimal or `0x`-prefixed
+/// hexadecimal u64 when building.
+pub const BUILD_SEED: u64 = build_seed::BUILD_SEED;
+
+mod build_seed {
+    include!(concat!(env!("OUT_DIR"), "/build_seed.rs"));
+}
+
+/// A Hasher seeded with [`BUILD_SEED`].
+pub type BuildSeeded<H> = Seeded<H, BUILD_SEED>;
+
 // ====================================
 // Hashing modules
 
+#[cfg(feature = "battery")]
+pub mod battery;
+#[cfg(feature = "cdc")]
+pub mod cdc;
+#[cfg(feature = "collisions")]
+pub mod collisions;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "frog")]
+pub mod frog;
+#[cfg(feature = "hardened")]
+pub mod hardened;
 #[cfg(feature = "jenkins")]
 pub mod jenkins;
+#[cfg(feature = "keysets")]
+pub mod keysets;
+#[cfg(feature = "merkle")]
+pub mod merkle;
+#[cfg(feature = "mixers")]
+pub mod mixers;
 #[cfg(feature = "oz")]
 pub mod oz;
+#[cfg(feature = "pearson")]
+pub mod pearson;
 #[cfg(feature = "pigeon")]
 pub mod pigeon;
+#[cfg(feature = "portable")]
+pub mod portable;
+#[cfg(feature = "probe")]
+pub mod probe;
+#[cfg(feature = "quality")]
+pub mod quality;
+#[cfg(feature = "std")]
+pub mod random;
+#[cfg(feature = "rolling")]
+pub mod rolling;
+#[cfg(feature = "tabulation")]
+pub mod tabulation;
+#[cfg(feature = "universal")]
+pub mod universal;
+#[cfg(feature = "visual")]
+pub mod visual;
+#[cfg(feature = "zobrist")]
+pub mod zobrist;
 
 /// For easy access, reexport the built-in hash map's DefaultHasher,
 /// including a matching one-stop function.
@@ -295,6 +641,12 @@ pub mod null {
             NullHasher
         }
 
+        /// The seed is ignored.
+        #[inline(always)]
+        pub const fn with_seed(_seed: u64) -> NullHasher {
+            NullHasher
+        }
+
         #[inline(always)]
         pub const fn finish(&self) -> u64 {
             0u64
@@ -307,6 +659,7 @@ pub mod null {
     }
 
     duplicate_const_traits!(NullHasher);
+    seedable_hasher!(NullHasher);
 
     hasher_to_fcn!(
         /// Provide access to NullHasher in a single call.
@@ -326,6 +679,12 @@ pub mod null {
             PassThroughHasher(0)
         }
 
+        /// The seed is the initial state.
+        #[inline(always)]
+        pub const fn with_seed(seed: u64) -> PassThroughHasher {
+            PassThroughHasher(seed)
+        }
+
         #[inline(always)]
         pub const fn finish(&self) -> u64 {
             self.0
@@ -335,18 +694,44 @@ pub mod null {
         pub const fn write(&mut self, bytes: &[u8]) {
             let mut i = 0;
             while i < bytes.len() {
-                self.0 = self.0.wrapping_shl(8) + (bytes[0] as u64);
+                self.0 = self.0.wrapping_shl(8) | (bytes[i] as u64);
+                i += 1;
             }
         }
     }
 
     duplicate_const_traits!(PassThroughHasher);
+    seedable_hasher!(PassThroughHasher);
 
     hasher_to_fcn!(
         /// Provide access to PassThroughHasher in a single call.
         passthrough,
         PassThroughHasher
     );
+
+    #[cfg(test)]
+    mod null_tests {
+        use super::*;
+
+        #[test]
+        fn basic() {
+            assert_eq!(null(b"abcd"), 0);
+            assert_eq!(passthrough(b""), 0);
+            assert_eq!(passthrough(b"\x01\x02"), 0x0102);
+        }
+
+        #[test]
+        fn passthrough_keeps_last_bytes() {
+            // write once looped forever, never advancing past the first byte
+            assert_eq!(
+                passthrough(&[1, 2, 3, 4, 5, 6, 7, 8, 9]),
+                0x0203040506070809
+            );
+            let mut h = PassThroughHasher::with_seed(0xff);
+            h.write(&[1]);
+            assert_eq!(h.finish(), 0xff01);
+        }
+    }
 }
 
 // ====================================
@@ -374,6 +759,7 @@ pub mod null {
 pub mod fnv {
     macro_rules! fnv1a {
         ($name:ident, $size:ty, $fnv_prime:expr, $offset_basis:expr) => {
+            #[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
             pub struct $name($size);
             impl $name {
                 #[inline(always)]
@@ -381,6 +767,20 @@ pub mod fnv {
                     $name($offset_basis)
                 }
 
+                /// Create a Hasher starting from `state` instead of the
+                /// offset basis.
+                #[inline(always)]
+                pub const fn new(state: $size) -> $name {
+                    $name(state)
+                }
+
+                /// The seed, truncated to the hash size, is xored into the
+                /// offset basis.
+                #[inline(always)]
+                pub const fn with_seed(seed: u64) -> $name {
+                    $name($offset_basis ^ (seed as $size))
+                }
+
                 #[inline(always)]
                 pub const fn finish(&self) -> u64 {
                     self.0 as u64
@@ -396,6 +796,7 @@ pub mod fnv {
                 }
             }
             duplicate_const_traits!($name);
+            seedable_hasher!($name);
         };
     }
 
@@ -408,6 +809,13 @@ pub mod fnv {
         FNV1aHasher32
     );
 
+    finalized_hasher!(
+        /// `FNV1aHasher32` through `fmix64`; see [`Finalized`](crate::mixers::Finalized).
+        FNV1aFinalized32,
+        FNV1aHasher32,
+        fnv1a32_finalized
+    );
+
     hasher_to_fcn!(
         /// Provide access to FNV1aHasher64 in a single call.
         fnv1a64,
@@ -427,5 +835,16 @@ pub mod fnv {
             assert_eq!(fnv1a64(b"abcd"), 18165163011005162717);
             assert_eq!(fnv1a64(b"abcdefg"), 4642726675185563447);
         }
+
+        #[test]
+        fn seeded() {
+            let mut h = FNV1aHasher64::with_seed(0);
+            h.write(b"abcd");
+            assert_eq!(h.finish(), fnv1a64(b"abcd"));
+            let mut h = FNV1aHasher32::with_seed(0);
+            h.write(b"abcd");
+            assert_eq!(h.finish(), fnv1a32(b"abcd"));
+            assert_ne!(FNV1aHasher64::with_seed(1).finish(), fnv1a64(b""));
+        }
     }
 }
@@ -0,0 +1,285 @@
+//! Merkle trees, from Ralph Merkle's *A Digital Signature Based on a
+//! Conventional Encryption Function* (CRYPTO '87).
+//!
+//! A Merkle tree hashes a sequence of leaves (blocks, or the chunks from
+//! [`cdc`](crate::cdc)) pairwise, or `k` at a time, up to a single root.
+//! Two replicas with equal roots almost certainly hold the same data, and
+//! when the roots differ, descending only into the subtrees whose hashes
+//! differ finds the changed leaves in `O(changes * log n)` comparisons.
+//!
+//! Leaves and interior nodes are hashed with different one-byte prefixes,
+//! as in RFC 6962 (Certificate Transparency), so an interior node can
+//! never be passed off as a leaf. None of the hashers here are
+//! cryptographic, so this detects accidents, not adversaries.
+
+use core::hash::Hasher;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::jenkins::spooky_hash::SpookyHasher;
+
+/// A 128-bit hash value.
+pub type Hash128 = (u64, u64);
+
+/// Prefix for leaf hashes.
+pub const LEAF_PREFIX: u8 = 0x00;
+/// Prefix for interior node hashes.
+pub const INTERIOR_PREFIX: u8 = 0x01;
+
+/// A Hasher that can produce a 128-bit result.
+pub trait Hasher128: Hasher {
+    fn finish128(&self) -> Hash128;
+}
+
+impl Hasher128 for SpookyHasher {
+    #[inline]
+    fn finish128(&self) -> Hash128 {
+        self.finish128()
+    }
+}
+
+/// Hash the contents of a leaf.
+pub fn leaf_hash<H: Hasher128 + Default>(data: &[u8]) -> Hash128 {
+    let mut h = H::default();
+    h.write_u8(LEAF_PREFIX);
+    h.write(data);
+    h.finish128()
+}
+
+/// Hash the children of an interior node.
+pub fn node_hash<H: Hasher128 + Default>(children: &[Hash128]) -> Hash128 {
+    let mut h = H::default();
+    h.write_u8(INTERIOR_PREFIX);
+    for (a, b) in children {
+        h.write(&a.to_le_bytes());
+        h.write(&b.to_le_bytes());
+    }
+    h.finish128()
+}
+
+/// A `k`-ary Merkle tree, keeping every level so that trees can be
+/// compared.
+pub struct MerkleTree<H = SpookyHasher> {
+    arity: usize,
+    // levels[0] are the leaf hashes, and the last level is the root.
+    levels: Vec<Vec<Hash128>>,
+    hasher: PhantomData<H>,
+}
+
+impl<H: Hasher128 + Default> MerkleTree<H> {
+    /// Build a tree over the contents of `leaves`.
+    pub fn from_leaves<'a, I>(arity: usize, leaves: I) -> MerkleTree<H>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let hashes = leaves.into_iter().map(leaf_hash::<H>).collect();
+        MerkleTree::from_leaf_hashes(arity, hashes)
+    }
+
+    /// Build a tree over leaves that have already been fingerprinted,
+    /// such as [`Chunk::fingerprint`](crate::cdc::Chunk::fingerprint).
+    /// Each fingerprint is hashed again as a leaf.
+    pub fn from_fingerprints<I>(arity: usize, fingerprints: I) -> MerkleTree<H>
+    where
+        I: IntoIterator<Item = Hash128>,
+    {
+        let hashes = fingerprints
+            .into_iter()
+            .map(|(a, b)| {
+                let mut bytes = [0u8; 16];
+                bytes[..8].copy_from_slice(&a.to_le_bytes());
+                bytes[8..].copy_from_slice(&b.to_le_bytes());
+                leaf_hash::<H>(&bytes)
+            })
+            .collect();
+        MerkleTree::from_leaf_hashes(arity, hashes)
+    }
+
+    /// Build a tree from leaf hashes, which are used as they are.
+    ///
+    /// The last node of a level may have fewer than `arity` children. The
+    /// root is always an interior node, even over zero or one leaves.
+    pub fn from_leaf_hashes(arity: usize, leaves: Vec<Hash128>) -> MerkleTree<H> {
+        assert!(
+            arity >= 2,
+            "a Merkle tree needs at least two children per node"
+        );
+        let mut levels = vec![leaves];
+        loop {
+            let below = levels.last().unwrap();
+            if levels.len() > 1 && below.len() == 1 {
+                break;
+            }
+            let level = if below.is_empty() {
+                vec![node_hash::<H>(&[])]
+            } else {
+                below.chunks(arity).map(node_hash::<H>).collect()
+            };
+            levels.push(level);
+        }
+        MerkleTree {
+            arity,
+            levels,
+            hasher: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    #[inline]
+    pub fn root(&self) -> Hash128 {
+        self.levels.last().unwrap()[0]
+    }
+
+    #[inline]
+    pub fn leaves(&self) -> &[Hash128] {
+        &self.levels[0]
+    }
+
+    /// The hashes at each level, from the leaves up to the root.
+    #[inline]
+    pub fn levels(&self) -> &[Vec<Hash128>] {
+        &self.levels
+    }
+
+    /// The ranges of leaf indices at which this tree and `other` differ,
+    /// including leaves present in only one of them. Adjacent differing
+    /// leaves are merged into one range.
+    pub fn diff(&self, other: &MerkleTree<H>) -> Vec<Range<usize>> {
+        assert_eq!(self.arity, other.arity, "trees must have the same arity");
+        // Node j at level l covers leaves j * arity^l .. (j + 1) * arity^l
+        // in both trees, so start at the highest level they share.
+        let top = self.levels.len().min(other.levels.len()) - 1;
+        let width = self.levels[top].len().max(other.levels[top].len());
+        let mut leaves = Vec::new();
+        for j in 0..width {
+            self.diff_node(other, top, j, &mut leaves);
+        }
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for i in leaves {
+            match ranges.last_mut() {
+                Some(r) if r.end == i => r.end += 1,
+                _ => ranges.push(i..i + 1),
+            }
+        }
+        ranges
+    }
+
+    fn diff_node(&self, other: &MerkleTree<H>, level: usize, j: usize, out: &mut Vec<usize>) {
+        let a = self.levels[level].get(j);
+        let b = other.levels[level].get(j);
+        if a == b {
+            return;
+        }
+        if level == 0 {
+            out.push(j);
+            return;
+        }
+        for child in j * self.arity..(j + 1) * self.arity {
+            self.diff_node(other, level - 1, child, out);
+        }
+    }
+}
+
+impl<H> Clone for MerkleTree<H> {
+    fn clone(&self) -> MerkleTree<H> {
+        MerkleTree {
+            arity: self.arity,
+            levels: self.levels.clone(),
+            hasher: PhantomData,
+        }
+    }
+}
+
+impl<H> core::fmt::Debug for MerkleTree<H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("arity", &self.arity)
+            .field("levels", &self.levels)
+            .finish()
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod merkle_tests {
+    use super::*;
+
+    fn blocks(n: usize) -> Vec<Vec<u8>> {
+        (0..n)
+            .map(|i| format!("block {}", i).into_bytes())
+            .collect()
+    }
+
+    fn tree(arity: usize, blocks: &[Vec<u8>]) -> MerkleTree {
+        MerkleTree::from_leaves(arity, blocks.iter().map(|b| b.as_slice()))
+    }
+
+    #[test]
+    fn basic() {
+        let b = blocks(5);
+        let t = tree(2, &b);
+        assert_eq!(t.levels().len(), 4);
+        assert_eq!(t.leaves()[0], leaf_hash::<SpookyHasher>(b"block 0"));
+        let n01 = node_hash::<SpookyHasher>(&t.leaves()[0..2]);
+        assert_eq!(t.levels()[1][0], n01);
+        assert_eq!(t.root(), tree(2, &b).root());
+        assert_ne!(t.root(), tree(4, &b).root());
+        assert_ne!(t.root(), tree(2, &b[..4]).root());
+        // Zero and one leaves still have an interior root.
+        assert_eq!(tree(2, &[]).root(), node_hash::<SpookyHasher>(&[]));
+        let one = tree(2, &b[..1]);
+        assert_eq!(one.root(), node_hash::<SpookyHasher>(one.leaves()));
+    }
+
+    #[test]
+    fn domain_separation() {
+        // A leaf whose contents are the encoding of two children does not
+        // hash like the interior node over those children.
+        let t = tree(2, &blocks(2));
+        let mut encoded = Vec::new();
+        for (a, b) in t.leaves() {
+            encoded.extend_from_slice(&a.to_le_bytes());
+            encoded.extend_from_slice(&b.to_le_bytes());
+        }
+        assert_ne!(leaf_hash::<SpookyHasher>(&encoded), t.root());
+    }
+
+    #[test]
+    fn diff() {
+        let a = blocks(20);
+        let mut b = a.clone();
+        b[3] = b"changed".to_vec();
+        b[4] = b"changed too".to_vec();
+        b[17] = b"also changed".to_vec();
+        for arity in [2, 3, 4] {
+            let (ta, tb) = (tree(arity, &a), tree(arity, &b));
+            assert!(ta.diff(&ta).is_empty());
+            assert_eq!(ta.diff(&tb), vec![3..5, 17..18]);
+            assert_eq!(tb.diff(&ta), vec![3..5, 17..18]);
+        }
+    }
+
+    #[test]
+    fn diff_lengths() {
+        let a = blocks(9);
+        let (ta, tb) = (tree(2, &a), tree(2, &a[..4]));
+        assert_eq!(ta.diff(&tb), vec![4..9]);
+        assert_eq!(tb.diff(&ta), vec![4..9]);
+        assert_eq!(ta.diff(&tree(2, &[])), vec![0..9]);
+    }
+
+    #[test]
+    fn fingerprints() {
+        let fps = [(1, 2), (3, 4), (5, 6)];
+        let t: MerkleTree = MerkleTree::from_fingerprints(2, fps);
+        let mut u: MerkleTree = MerkleTree::from_fingerprints(2, fps);
+        assert_eq!(t.root(), u.root());
+        u = MerkleTree::from_fingerprints(2, [(1, 2), (3, 5), (5, 6)]);
+        assert_eq!(t.diff(&u), vec![1..2]);
+    }
+}
@@ -0,0 +1,945 @@
+//! Integer mixers (a.k.a. finalizers, integer hash functions).
+//!
+//! Several of the Hashers in this crate end with a finalizer that
+//! scrambles the bits of the internal state: `OAATHasher::finish`,
+//! `Lookup3Hasher::final_mix`, SpookyHash's `end_partial`. This module
+//! collects the well-known stand-alone versions of those: functions
+//! that take a single integer and return a well-mixed integer of the
+//! same width.
+//!
+//! - SplitMix64, from Steele, Lea and Flood's *Fast Splittable
+//!   Pseudorandom Number Generators* (2014).
+//! - `fmix32` and `fmix64`, the finalizers of Austin Appleby's
+//!   MurmurHash3.
+//! - David Stafford's Mix01 through Mix14, variants of `fmix64` with
+//!   constants found by search.
+//!   http://zimbry.blogspot.com/2011/09/better-bit-mixing-improving-on.html
+//! - Thomas Wang's and Bob Jenkins' integer hashes.
+//!   http://web.archive.org/web/20071223173210/http://www.concentric.net/~Ttwang/tech/inthash.htm
+//! - degski's xorshift-multiply mixes.
+//!   https://stackoverflow.com/a/12996028
+//! - Fibonacci (multiplicative) hashing, from Knuth.
+//!
+//! All of these are bijections on their integer type, so each comes with
+//! an `unmix` inverse: `unfmix64(fmix64(x)) == x`. The inverses are
+//! handy for debugging and for building inputs that hash to a chosen
+//! value; they are not fast.
+//!
+//! The 64-bit mixers are also available as zero-sized types
+//! implementing [`Mixer`], for use with [`IntegerHasher`] and with
+//! [`Finalized`], which mixes the result of another Hasher.
+
+use core::hash::Hasher;
+use core::marker::PhantomData;
+
+// ====================================
+// Inversion helpers
+
+/// The multiplicative inverse of an odd `a`, modulo 2^64.
+///
+/// Newton's iteration; each step doubles the number of correct low bits,
+/// starting from the 3 bits given by `a * a == 1 (mod 8)`.
+pub const fn inverse_u64(a: u64) -> u64 {
+    debug_assert!(a & 1 == 1);
+    let mut x = a;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(a.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
+
+/// The multiplicative inverse of an odd `a`, modulo 2^32.
+pub const fn inverse_u32(a: u32) -> u32 {
+    debug_assert!(a & 1 == 1);
+    let mut x = a;
+    let mut i = 0;
+    while i < 4 {
+        x = x.wrapping_mul(2u32.wrapping_sub(a.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
+
+/// Invert `x ^= x >> shift`, for `shift` from 1 to 63. (With a shift of
+/// 0 the xorshift clears `x`, and has no inverse.)
+#[inline]
+pub const fn unxorshift_right64(y: u64, shift: u32) -> u64 {
+    assert!(shift > 0 && shift < 64, "xorshift by 0 or the width");
+    let mut x = y;
+    let mut i = shift;
+    while i < 64 {
+        x = y ^ (x >> shift);
+        i += shift;
+    }
+    x
+}
+
+/// Invert `x ^= x << shift`, for `shift` from 1 to 63. (With a shift of
+/// 0 the xorshift clears `x`, and has no inverse.)
+#[inline]
+pub const fn unxorshift_left64(y: u64, shift: u32) -> u64 {
+    assert!(shift > 0 && shift < 64, "xorshift by 0 or the width");
+    let mut x = y;
+    let mut i = shift;
+    while i < 64 {
+        x = y ^ (x << shift);
+        i += shift;
+    }
+    x
+}
+
+/// Invert `x ^= x >> shift`, for `shift` from 1 to 31. (With a shift of
+/// 0 the xorshift clears `x`, and has no inverse.)
+#[inline]
+pub const fn unxorshift_right32(y: u32, shift: u32) -> u32 {
+    assert!(shift > 0 && shift < 32, "xorshift by 0 or the width");
+    let mut x = y;
+    let mut i = shift;
+    while i < 32 {
+        x = y ^ (x >> shift);
+        i += shift;
+    }
+    x
+}
+
+// ====================================
+// SplitMix64
+
+/// The increment of the SplitMix64 generator: 2^64 / φ, rounded to odd.
+pub const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+/// The SplitMix64 hash of a single value: one step of the generator
+/// seeded with `x`.
+///
+/// The output function is Stafford's Mix13.
+#[inline]
+pub const fn splitmix64(x: u64) -> u64 {
+    mix13(x.wrapping_add(GOLDEN_GAMMA))
+}
+
+/// Inverse of [`splitmix64`].
+#[inline]
+pub const fn unsplitmix64(x: u64) -> u64 {
+    unmix13(x).wrapping_sub(GOLDEN_GAMMA)
+}
+
+/// The SplitMix64 pseudorandom generator.
+///
+/// Not a Hasher; this is here because it is the usual way to turn a
+/// single seed into a stream of well-mixed words, and is used that way
+/// elsewhere in this crate.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    #[inline(always)]
+    pub const fn new(seed: u64) -> SplitMix64 {
+        SplitMix64(seed)
+    }
+
+    /// Return the next 64-bit output.
+    #[inline(always)]
+    pub const fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(GOLDEN_GAMMA);
+        mix13(self.0)
+    }
+}
+
+// ====================================
+// MurmurHash3 finalizers
+
+/// > Finalization mix - force all bits of a hash block to avalanche
+///
+/// The 32-bit finalizer from MurmurHash3.
+#[inline]
+pub const fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^= h >> 16;
+    h
+}
+
+/// Inverse of [`fmix32`].
+#[inline]
+pub const fn unfmix32(mut h: u32) -> u32 {
+    h = unxorshift_right32(h, 16);
+    h = h.wrapping_mul(inverse_u32(0xc2b2ae35));
+    h = unxorshift_right32(h, 13);
+    h = h.wrapping_mul(inverse_u32(0x85ebca6b));
+    h = unxorshift_right32(h, 16);
+    h
+}
+
+/// The 64-bit finalizer from MurmurHash3.
+#[inline]
+pub const fn fmix64(k: u64) -> u64 {
+    stafford(k, 33, 0xff51afd7ed558ccd, 33, 0xc4ceb9fe1a85ec53, 33)
+}
+
+/// Inverse of [`fmix64`].
+#[inline]
+pub const fn unfmix64(k: u64) -> u64 {
+    unstafford(k, 33, 0xff51afd7ed558ccd, 33, 0xc4ceb9fe1a85ec53, 33)
+}
+
+// ====================================
+// Stafford's variants
+
+/// The xorshift-multiply-xorshift-multiply-xorshift shape shared by
+/// `fmix64` and Stafford's mixes.
+#[inline(always)]
+const fn stafford(mut z: u64, s1: u32, m1: u64, s2: u32, m2: u64, s3: u32) -> u64 {
+    z ^= z >> s1;
+    z = z.wrapping_mul(m1);
+    z ^= z >> s2;
+    z = z.wrapping_mul(m2);
+    z ^= z >> s3;
+    z
+}
+
+#[inline(always)]
+const fn unstafford(mut z: u64, s1: u32, m1: u64, s2: u32, m2: u64, s3: u32) -> u64 {
+    z = unxorshift_right64(z, s3);
+    z = z.wrapping_mul(inverse_u64(m2));
+    z = unxorshift_right64(z, s2);
+    z = z.wrapping_mul(inverse_u64(m1));
+    z = unxorshift_right64(z, s1);
+    z
+}
+
+/// Stafford's parameters: shift, multiplier, shift, multiplier, shift.
+///
+/// From http://zimbry.blogspot.com/2011/09/better-bit-mixing-improving-on.html.
+/// Mix13 is the one used by SplitMix64.
+#[rustfmt::skip]
+const STAFFORD: [(u32, u64, u32, u64, u32); 14] = [
+    (31, 0x7fb5d329728ea185, 27, 0x81dadef4bc2dd44d, 33),
+    (33, 0x64dd81482cbd31d7, 31, 0xe36aa5c613612997, 31),
+    (31, 0x99bcf6822b23ca35, 30, 0x14020a57acced8b7, 33),
+    (33, 0x62a9d9ed799705f5, 28, 0xcb24d0a5c88c35b3, 32),
+    (31, 0x79c135c1674b9add, 29, 0x54c77c86f6913e45, 30),
+    (31, 0x69b0bc90bd9a8c49, 27, 0x3d5e661a2a77868d, 30),
+    (30, 0x16a6ac37883af045, 26, 0xcc9c31a4274686a5, 32),
+    (30, 0x294aa62849912f0b, 28, 0x0a9ba9c8a5b15117, 31),
+    (32, 0x4cd6944c5cc20b6d, 29, 0xfc12c5b19d3259e9, 32),
+    (30, 0xe4c7e495f4c683f5, 32, 0xfda871baea35a293, 33),
+    (27, 0x97d461a8b11570d9, 28, 0x02271eb7c6c4cd6b, 32),
+    (29, 0x3cd0eb9d47532dfb, 26, 0x63660277528772bb, 33),
+    (30, 0xbf58476d1ce4e5b9, 27, 0x94d049bb133111eb, 31),
+    (30, 0x4be98134a5976fd3, 29, 0x3bc0993a5ad19a13, 31),
+];
+
+macro_rules! stafford_mix {
+    ($mix:ident, $unmix:ident, $n:expr) => {
+        #[doc = concat!("Stafford's ", stringify!($mix), ".")]
+        #[inline]
+        pub const fn $mix(z: u64) -> u64 {
+            let (s1, m1, s2, m2, s3) = STAFFORD[$n - 1];
+            stafford(z, s1, m1, s2, m2, s3)
+        }
+
+        #[doc = concat!("Inverse of [`", stringify!($mix), "`].")]
+        #[inline]
+        pub const fn $unmix(z: u64) -> u64 {
+            let (s1, m1, s2, m2, s3) = STAFFORD[$n - 1];
+            unstafford(z, s1, m1, s2, m2, s3)
+        }
+    };
+}
+
+stafford_mix!(mix01, unmix01, 1);
+stafford_mix!(mix02, unmix02, 2);
+stafford_mix!(mix03, unmix03, 3);
+stafford_mix!(mix04, unmix04, 4);
+stafford_mix!(mix05, unmix05, 5);
+stafford_mix!(mix06, unmix06, 6);
+stafford_mix!(mix07, unmix07, 7);
+stafford_mix!(mix08, unmix08, 8);
+stafford_mix!(mix09, unmix09, 9);
+stafford_mix!(mix10, unmix10, 10);
+stafford_mix!(mix11, unmix11, 11);
+stafford_mix!(mix12, unmix12, 12);
+stafford_mix!(mix13, unmix13, 13);
+stafford_mix!(mix14, unmix14, 14);
+
+// ====================================
+// Wang and Jenkins
+
+/// Thomas Wang's 64-bit integer hash, `hash64shift`.
+///
+/// From Wang's page:
+///
+/// > The key to a good hash function is the mixing of bits. ... A good
+/// > mixing function must be reversible.
+#[inline]
+pub const fn wang64(mut key: u64) -> u64 {
+    key = (!key).wrapping_add(key << 21); // key * (2^21 - 1) - 1
+    key ^= key >> 24;
+    key = key.wrapping_mul(265); // key + (key << 3) + (key << 8)
+    key ^= key >> 14;
+    key = key.wrapping_mul(21); // key + (key << 2) + (key << 4)
+    key ^= key >> 28;
+    key = key.wrapping_add(key << 31);
+    key
+}
+
+/// Inverse of [`wang64`].
+#[inline]
+pub const fn unwang64(mut key: u64) -> u64 {
+    key = key.wrapping_mul(inverse_u64((1 << 31) + 1));
+    key = unxorshift_right64(key, 28);
+    key = key.wrapping_mul(inverse_u64(21));
+    key = unxorshift_right64(key, 14);
+    key = key.wrapping_mul(inverse_u64(265));
+    key = unxorshift_right64(key, 24);
+    key = key.wrapping_add(1).wrapping_mul(inverse_u64((1 << 21) - 1));
+    key
+}
+
+/// Thomas Wang's 32-bit integer hash, `hash32shift`.
+#[inline]
+pub const fn wang32(mut key: u32) -> u32 {
+    key = (!key).wrapping_add(key << 15); // key * (2^15 - 1) - 1
+    key ^= key >> 12;
+    key = key.wrapping_mul(5); // key + (key << 2)
+    key ^= key >> 4;
+    key = key.wrapping_mul(2057); // key + (key << 3) + (key << 11)
+    key ^= key >> 16;
+    key
+}
+
+/// Inverse of [`wang32`].
+#[inline]
+pub const fn unwang32(mut key: u32) -> u32 {
+    key = unxorshift_right32(key, 16);
+    key = key.wrapping_mul(inverse_u32(2057));
+    key = unxorshift_right32(key, 4);
+    key = key.wrapping_mul(inverse_u32(5));
+    key = unxorshift_right32(key, 12);
+    key = key.wrapping_add(1).wrapping_mul(inverse_u32((1 << 15) - 1));
+    key
+}
+
+/// Bob Jenkins' 32-bit integer hash, as given on Wang's page.
+#[inline]
+pub const fn jenkins32(mut a: u32) -> u32 {
+    a = a.wrapping_add(0x7ed55d16).wrapping_add(a << 12);
+    a = (a ^ 0xc761c23c) ^ (a >> 19);
+    a = a.wrapping_add(0x165667b1).wrapping_add(a << 5);
+    a = a.wrapping_add(0xd3a2646c) ^ (a << 9);
+    a = a.wrapping_add(0xfd7046c5).wrapping_add(a << 3);
+    a = (a ^ 0xb55a4f09) ^ (a >> 16);
+    a
+}
+
+/// Inverse of [`jenkins32`].
+pub const fn unjenkins32(mut a: u32) -> u32 {
+    a = unxorshift_right32(a ^ 0xb55a4f09, 16);
+    a = a.wrapping_sub(0xfd7046c5).wrapping_mul(inverse_u32(9));
+    // `(a + c) ^ (a << 9)`: bit i of the result depends only on bits
+    // 0..=i of a, and flipping bit i of a flips bit i of the result, so
+    // recover a one bit at a time from the bottom.
+    let target = a;
+    a = 0;
+    let mut i = 0;
+    while i < 32 {
+        let y = a.wrapping_add(0xd3a2646c) ^ (a << 9);
+        if ((y ^ target) >> i) & 1 == 1 {
+            a |= 1 << i;
+        }
+        i += 1;
+    }
+    a = a.wrapping_sub(0x165667b1).wrapping_mul(inverse_u32(33));
+    a = unxorshift_right32(a ^ 0xc761c23c, 19);
+    a = a.wrapping_sub(0x7ed55d16).wrapping_mul(inverse_u32(4097));
+    a
+}
+
+// ====================================
+// degski
+
+/// degski's 64-bit xorshift-multiply mix.
+#[inline]
+pub const fn degski64(mut x: u64) -> u64 {
+    x ^= x >> 32;
+    x = x.wrapping_mul(0xd6e8feb86659fd93);
+    x ^= x >> 32;
+    x = x.wrapping_mul(0xd6e8feb86659fd93);
+    x ^= x >> 32;
+    x
+}
+
+/// Inverse of [`degski64`].
+#[inline]
+pub const fn undegski64(mut x: u64) -> u64 {
+    x ^= x >> 32;
+    x = x.wrapping_mul(inverse_u64(0xd6e8feb86659fd93));
+    x ^= x >> 32;
+    x = x.wrapping_mul(inverse_u64(0xd6e8feb86659fd93));
+    x ^= x >> 32;
+    x
+}
+
+/// degski's 32-bit xorshift-multiply mix.
+#[inline]
+pub const fn degski32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x45d9f3b);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x45d9f3b);
+    x ^= x >> 16;
+    x
+}
+
+/// Inverse of [`degski32`].
+#[inline]
+pub const fn undegski32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(inverse_u32(0x45d9f3b));
+    x ^= x >> 16;
+    x = x.wrapping_mul(inverse_u32(0x45d9f3b));
+    x ^= x >> 16;
+    x
+}
+
+// ====================================
+// Fibonacci hashing
+
+/// 2^32 / φ, rounded to odd.
+pub const FIBONACCI32: u32 = 0x9e3779b9;
+/// 2^64 / φ, rounded to odd.
+pub const FIBONACCI64: u64 = 0x9e3779b97f4a7c15;
+
+/// Multiply by 2^64 / φ.
+///
+/// On its own this only mixes upward: the high bits of the result depend
+/// on all of the input, the low bits do not. Use [`fibonacci_hash64`] to
+/// take the high bits.
+#[inline]
+pub const fn fibonacci64(x: u64) -> u64 {
+    x.wrapping_mul(FIBONACCI64)
+}
+
+/// Inverse of [`fibonacci64`].
+#[inline]
+pub const fn unfibonacci64(x: u64) -> u64 {
+    x.wrapping_mul(inverse_u64(FIBONACCI64))
+}
+
+/// Multiply by 2^32 / φ.
+#[inline]
+pub const fn fibonacci32(x: u32) -> u32 {
+    x.wrapping_mul(FIBONACCI32)
+}
+
+/// Inverse of [`fibonacci32`].
+#[inline]
+pub const fn unfibonacci32(x: u32) -> u32 {
+    x.wrapping_mul(inverse_u32(FIBONACCI32))
+}
+
+/// Knuth's multiplicative hashing: the top `bits` bits of
+/// [`fibonacci64`], suitable for indexing a table of 2^`bits` entries.
+/// Not invertible.
+#[inline]
+pub const fn fibonacci_hash64(x: u64, bits: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        fibonacci64(x) >> (64 - bits)
+    }
+}
+
+/// The top `bits` bits of [`fibonacci32`]. Not invertible.
+#[inline]
+pub const fn fibonacci_hash32(x: u32, bits: u32) -> u32 {
+    if bits == 0 {
+        0
+    } else {
+        fibonacci32(x) >> (32 - bits)
+    }
+}
+
+// ====================================
+// Mixer types
+
+/// A 64-bit bijective mixer, as a type.
+///
+/// Trait methods cannot be `const`, so this is the non-const face of the
+/// functions above, for use as a type parameter.
+pub trait Mixer {
+    fn mix(x: u64) -> u64;
+    fn unmix(x: u64) -> u64;
+}
+
+macro_rules! mixer_type {
+    ($(#[$attr:meta])* $name:ident, $mix:ident, $unmix:ident) => {
+        $(#[$attr])*
+        #[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Default, Hash)]
+        pub struct $name;
+
+        impl Mixer for $name {
+            #[inline(always)]
+            fn mix(x: u64) -> u64 {
+                $mix(x)
+            }
+
+            #[inline(always)]
+            fn unmix(x: u64) -> u64 {
+                $unmix(x)
+            }
+        }
+    };
+}
+
+mixer_type!(
+    /// [`splitmix64`] as a type.
+    SplitMix,
+    splitmix64,
+    unsplitmix64
+);
+mixer_type!(
+    /// [`fmix64`] as a type.
+    Murmur3,
+    fmix64,
+    unfmix64
+);
+mixer_type!(
+    /// [`wang64`] as a type.
+    Wang,
+    wang64,
+    unwang64
+);
+mixer_type!(
+    /// [`degski64`] as a type.
+    Degski,
+    degski64,
+    undegski64
+);
+mixer_type!(
+    /// [`fibonacci64`] as a type.
+    Fibonacci,
+    fibonacci64,
+    unfibonacci64
+);
+mixer_type!(Mix01, mix01, unmix01);
+mixer_type!(Mix02, mix02, unmix02);
+mixer_type!(Mix03, mix03, unmix03);
+mixer_type!(Mix04, mix04, unmix04);
+mixer_type!(Mix05, mix05, unmix05);
+mixer_type!(Mix06, mix06, unmix06);
+mixer_type!(Mix07, mix07, unmix07);
+mixer_type!(Mix08, mix08, unmix08);
+mixer_type!(Mix09, mix09, unmix09);
+mixer_type!(Mix10, mix10, unmix10);
+mixer_type!(Mix11, mix11, unmix11);
+mixer_type!(Mix12, mix12, unmix12);
+mixer_type!(Mix13, mix13, unmix13);
+mixer_type!(Mix14, mix14, unmix14);
+
+// ====================================
+// IntegerHasher
+
+/// A Hasher for integer keys: each integer written is folded into the
+/// state and run through the mixer `M`.
+///
+/// For a single `write_u64(x)` (or any narrower integer), `finish()` is
+/// exactly `M::mix(x)`, so e.g. `IntegerHasher<Murmur3>` hashes a `u64`
+/// key to `fmix64(key)`. Byte slices are consumed as little-endian 8-byte
+/// words, the last one zero-padded, so they work but are not what this
+/// is for.
+pub struct IntegerHasher<M> {
+    pub hash: u64,
+    mixer: PhantomData<M>,
+}
+
+impl<M> IntegerHasher<M> {
+    #[inline(always)]
+    pub const fn default() -> IntegerHasher<M> {
+        IntegerHasher {
+            hash: 0,
+            mixer: PhantomData,
+        }
+    }
+
+    /// The seed is the initial state, so a single `write_u64(x)` hashes to
+    /// `M::mix(seed ^ x)`.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> IntegerHasher<M> {
+        IntegerHasher {
+            hash: seed,
+            mixer: PhantomData,
+        }
+    }
+
+    /// Create a Hasher seeded with [`BUILD_SEED`](crate::BUILD_SEED).
+    #[inline(always)]
+    pub const fn with_build_seed() -> IntegerHasher<M> {
+        IntegerHasher::with_seed(crate::BUILD_SEED)
+    }
+}
+
+impl<M: Mixer> IntegerHasher<M> {
+    #[inline(always)]
+    fn mix_in(&mut self, x: u64) {
+        self.hash = M::mix(self.hash ^ x);
+    }
+}
+
+impl<M> Default for IntegerHasher<M> {
+    #[inline]
+    fn default() -> IntegerHasher<M> {
+        IntegerHasher::default()
+    }
+}
+
+impl<M: Mixer> crate::SeedableHasher for IntegerHasher<M> {
+    #[inline(always)]
+    fn with_seed(seed: u64) -> IntegerHasher<M> {
+        IntegerHasher::with_seed(seed)
+    }
+}
+
+impl<M, const SEED: u64> crate::Seeded<IntegerHasher<M>, SEED> {
+    #[inline(always)]
+    pub const fn default() -> crate::Seeded<IntegerHasher<M>, SEED> {
+        crate::Seeded(IntegerHasher::with_seed(SEED))
+    }
+}
+
+impl<M> Clone for IntegerHasher<M> {
+    #[inline]
+    fn clone(&self) -> IntegerHasher<M> {
+        *self
+    }
+}
+
+impl<M> Copy for IntegerHasher<M> {}
+
+impl<M> core::fmt::Debug for IntegerHasher<M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IntegerHasher")
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+impl<M: Mixer> Hasher for IntegerHasher<M> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.mix_in(load_int_le!(chunk, 0, u64));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut buf = [0u8; 8];
+            buf[..rest.len()].copy_from_slice(rest);
+            self.mix_in(u64::from_le_bytes(buf));
+        }
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, i: u8) {
+        self.mix_in(i as u64);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, i: u16) {
+        self.mix_in(i as u64);
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, i: u32) {
+        self.mix_in(i as u64);
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, i: u64) {
+        self.mix_in(i);
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, i: usize) {
+        self.mix_in(i as u64);
+    }
+}
+
+// ====================================
+// Finalized
+
+/// A Hasher adapter that runs `H`'s result through the mixer `M`.
+///
+/// Several Hashers in this crate return 32 bits or fewer in a `u64`:
+/// `DJB2Hasher`, `SDBMHasher`, `OAATHasher` and `FNV1aHasher32` leave the
+/// upper 32 bits zero, and `LoseLoseHasher`'s sums are rarely more than a
+/// few thousand. std's `HashMap` (hashbrown, a SwissTable) takes its 7-bit
+/// control tag from the top of the hash and the bucket from the bottom,
+/// so with these Hashers every entry has the same tag and lookups compare
+/// keys along long probe sequences. `Finalized<DJB2Hasher, Murmur3>`
+/// spreads the 32 bits over all 64 with `fmix64`.
+///
+/// The mixers are bijections, so finalizing adds no collisions; it can't
+/// remove any either, and a 32-bit Hasher still collides as often as any
+/// 32-bit function. Writes are passed to `H` unchanged.
+///
+/// The aliases for these Hashers (`DJB2Finalized`, `SDBMFinalized`,
+/// `LoseLoseFinalized`, `OAATFinalized` and `FNV1aFinalized32`) also have
+/// `const fn` `default`, `with_seed`, `write` and `finish`, and single-call
+/// functions like `djb2_finalized`.
+pub struct Finalized<H, M> {
+    pub hasher: H,
+    mixer: PhantomData<M>,
+}
+
+impl<H, M> Finalized<H, M> {
+    #[inline(always)]
+    pub const fn new(hasher: H) -> Finalized<H, M> {
+        Finalized {
+            hasher,
+            mixer: PhantomData,
+        }
+    }
+}
+
+impl<H: Default, M> Default for Finalized<H, M> {
+    #[inline]
+    fn default() -> Finalized<H, M> {
+        Finalized::new(H::default())
+    }
+}
+
+impl<H: crate::SeedableHasher, M: Mixer> crate::SeedableHasher for Finalized<H, M> {
+    #[inline(always)]
+    fn with_seed(seed: u64) -> Finalized<H, M> {
+        Finalized::new(H::with_seed(seed))
+    }
+
+    #[inline(always)]
+    fn with_seed128(seed: u128) -> Finalized<H, M> {
+        Finalized::new(H::with_seed128(seed))
+    }
+}
+
+impl<H: Clone, M> Clone for Finalized<H, M> {
+    #[inline]
+    fn clone(&self) -> Finalized<H, M> {
+        Finalized::new(self.hasher.clone())
+    }
+}
+
+impl<H: Copy, M> Copy for Finalized<H, M> {}
+
+impl<H: PartialEq, M> PartialEq for Finalized<H, M> {
+    #[inline]
+    fn eq(&self, other: &Finalized<H, M>) -> bool {
+        self.hasher == other.hasher
+    }
+}
+
+impl<H: Eq, M> Eq for Finalized<H, M> {}
+
+impl<H: core::fmt::Debug, M> core::fmt::Debug for Finalized<H, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Finalized")
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
+
+impl<H: Hasher, M: Mixer> Hasher for Finalized<H, M> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        M::mix(self.hasher.finish())
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod mixers_tests {
+    use super::*;
+
+    type Pair64 = (fn(u64) -> u64, fn(u64) -> u64);
+    type Pair32 = (fn(u32) -> u32, fn(u32) -> u32);
+
+    const SAMPLES: [u64; 8] = [
+        0,
+        1,
+        2,
+        0xff,
+        0xdeadbeef,
+        0x0123456789abcdef,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    #[test]
+    fn splitmix64_reference() {
+        // First outputs of the reference generator seeded with 0.
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next(), 0xe220a8397b1dcdaf);
+        assert_eq!(rng.next(), 0x6e789e6aa1b965f4);
+        assert_eq!(rng.next(), 0x06c45d188009454f);
+        assert_eq!(splitmix64(0), 0xe220a8397b1dcdaf);
+    }
+
+    #[test]
+    fn fmix32_reference() {
+        // MurmurHash3_x86_32 of the empty string with seed 1 is fmix32(1).
+        assert_eq!(fmix32(1), 0x514e28b7);
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(inverse_u64(FIBONACCI64).wrapping_mul(FIBONACCI64), 1);
+        assert_eq!(inverse_u32(FIBONACCI32).wrapping_mul(FIBONACCI32), 1);
+        let fns: [Pair64; 19] = [
+            (splitmix64, unsplitmix64),
+            (fmix64, unfmix64),
+            (wang64, unwang64),
+            (degski64, undegski64),
+            (fibonacci64, unfibonacci64),
+            (mix01, unmix01),
+            (mix02, unmix02),
+            (mix03, unmix03),
+            (mix04, unmix04),
+            (mix05, unmix05),
+            (mix06, unmix06),
+            (mix07, unmix07),
+            (mix08, unmix08),
+            (mix09, unmix09),
+            (mix10, unmix10),
+            (mix11, unmix11),
+            (mix12, unmix12),
+            (mix13, unmix13),
+            (mix14, unmix14),
+        ];
+        for (mix, unmix) in fns {
+            for x in SAMPLES {
+                assert_eq!(unmix(mix(x)), x);
+                assert_eq!(mix(unmix(x)), x);
+            }
+        }
+        let fns: [Pair32; 5] = [
+            (fmix32, unfmix32),
+            (wang32, unwang32),
+            (jenkins32, unjenkins32),
+            (degski32, undegski32),
+            (fibonacci32, unfibonacci32),
+        ];
+        for (mix, unmix) in fns {
+            for x in SAMPLES {
+                let x = x as u32;
+                assert_eq!(unmix(mix(x)), x);
+                assert_eq!(mix(unmix(x)), x);
+            }
+        }
+    }
+
+    #[test]
+    fn fibonacci_hash() {
+        assert_eq!(fibonacci_hash64(1, 0), 0);
+        assert_eq!(fibonacci_hash64(1, 64), FIBONACCI64);
+        assert_eq!(fibonacci_hash64(1, 8), 0x9e);
+        assert_eq!(fibonacci_hash32(1, 8), 0x9e);
+    }
+
+    #[test]
+    fn integer_hasher() {
+        let mut h = IntegerHasher::<Murmur3>::default();
+        h.write_u64(0xdeadbeef);
+        assert_eq!(h.finish(), fmix64(0xdeadbeef));
+
+        let mut h = IntegerHasher::<Mix13>::default();
+        h.write_u32(7);
+        assert_eq!(h.finish(), mix13(7));
+
+        let mut h = IntegerHasher::<Wang>::default();
+        h.write(&[1, 2, 3]);
+        assert_eq!(h.finish(), wang64(0x030201));
+    }
+
+    #[test]
+    fn finalized() {
+        struct Low(u64);
+        impl Hasher for Low {
+            fn finish(&self) -> u64 {
+                self.0 & 0xffff_ffff
+            }
+            fn write(&mut self, bytes: &[u8]) {
+                for &b in bytes {
+                    self.0 = self.0.wrapping_mul(33) ^ b as u64;
+                }
+            }
+        }
+
+        let mut h = Finalized::<Low, Murmur3>::new(Low(5381));
+        h.write(b"abc");
+        h.write_u32(7);
+        let mut low = Low(5381);
+        low.write(b"abc");
+        low.write_u32(7);
+        assert_eq!(h.finish(), fmix64(low.finish()));
+        assert_eq!(unfmix64(h.finish()), low.finish());
+        assert_ne!(h.finish() >> 32, 0);
+    }
+
+    #[test]
+    fn const_eval() {
+        const H: u64 = fmix64(42);
+        const U: u64 = unfmix64(H);
+        assert_eq!(U, 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "xorshift by 0")]
+    fn unxorshift_by_zero() {
+        // used to loop forever
+        unxorshift_right64(42, 0);
+    }
+}
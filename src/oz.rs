@@ -56,12 +56,21 @@ impl DJB2Hasher {
         DJB2Hasher(5381)
     }
 
-    /// Create a new DJB2Hasher with a specific seed.
+    /// Create a DJB2Hasher whose state is `seed`, in place of 5381.
+    /// Unlike [`with_seed`](Self::with_seed), which keeps the reference
+    /// initial value for a seed of 0, this replaces it.
     #[inline(always)]
     pub const fn new(seed: u32) -> DJB2Hasher {
         DJB2Hasher(seed)
     }
 
+    /// The low 32 bits of the seed are xored into the reference initial
+    /// value, 5381.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> DJB2Hasher {
+        DJB2Hasher(5381 ^ seed as u32)
+    }
+
     #[inline(always)]
     pub const fn finish(&self) -> u64 {
         self.0 as u64
@@ -78,18 +87,32 @@ impl DJB2Hasher {
 }
 
 duplicate_const_traits!(DJB2Hasher);
+seedable_hasher!(DJB2Hasher);
 hasher_to_fcn!(
     /// Provide access to DJB2Hasher in a single call.
     djb2,
     DJB2Hasher
 );
 
+finalized_hasher!(
+    /// `DJB2Hasher` through `fmix64`; see [`Finalized`](crate::mixers::Finalized).
+    DJB2Finalized,
+    DJB2Hasher,
+    djb2_finalized
+);
+
 // ------------------------------------
 
 #[cfg(test)]
 mod djb2_tests {
     use super::*;
 
+    #[test]
+    fn seeded() {
+        assert_eq!(DJB2Hasher::with_seed(0), DJB2Hasher::default());
+        assert_ne!(DJB2Hasher::with_seed(1), DJB2Hasher::default());
+    }
+
     #[test]
     fn basic() {
         assert_eq!(djb2(b""), 5381);
@@ -97,6 +120,25 @@ mod djb2_tests {
         assert_eq!(djb2(b"b"), 177607);
         assert_eq!(djb2(b"ab"), 5860902);
     }
+
+    #[cfg(feature = "mixers")]
+    #[test]
+    fn finalized() {
+        use crate::mixers::fmix64;
+        use core::hash::{BuildHasher, BuildHasherDefault};
+
+        const HASH: u64 = {
+            let mut h = DJB2Finalized::with_seed(0);
+            h.write(b"ab");
+            h.finish()
+        };
+        assert_eq!(HASH, fmix64(5860902));
+        assert_eq!(djb2_finalized(b"ab"), HASH);
+        let build = BuildHasherDefault::<DJB2Finalized>::default();
+        let mut h = build.build_hasher();
+        core::hash::Hasher::write(&mut h, b"ab");
+        assert_eq!(core::hash::Hasher::finish(&h), HASH);
+    }
 }
 
 // ====================================
@@ -127,6 +169,12 @@ impl SDBMHasher {
         SDBMHasher(0)
     }
 
+    /// The low 32 bits of the seed are the initial state.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> SDBMHasher {
+        SDBMHasher(seed as u32)
+    }
+
     #[inline(always)]
     pub const fn finish(&self) -> u64 {
         self.0 as u64
@@ -146,6 +194,7 @@ impl SDBMHasher {
 }
 
 duplicate_const_traits!(SDBMHasher);
+seedable_hasher!(SDBMHasher);
 
 hasher_to_fcn!(
     /// Provide access to SDBMHasher in a single call.
@@ -153,6 +202,13 @@ hasher_to_fcn!(
     SDBMHasher
 );
 
+finalized_hasher!(
+    /// `SDBMHasher` through `fmix64`; see [`Finalized`](crate::mixers::Finalized).
+    SDBMFinalized,
+    SDBMHasher,
+    sdbm_finalized
+);
+
 // ------------------------------------
 
 #[cfg(test)]
@@ -195,6 +251,14 @@ impl LoseLoseHasher {
         LoseLoseHasher(0)
     }
 
+    /// The seed is the initial state. Since LoseLose only adds bytes, the
+    /// seed shifts every hash by the same amount and does not change which
+    /// inputs collide.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> LoseLoseHasher {
+        LoseLoseHasher(seed)
+    }
+
     #[inline(always)]
     pub const fn finish(&self) -> u64 {
         self.0
@@ -211,6 +275,7 @@ impl LoseLoseHasher {
 }
 
 duplicate_const_traits!(LoseLoseHasher);
+seedable_hasher!(LoseLoseHasher);
 
 hasher_to_fcn!(
     /// Provide access to LoseLoseHasher in a single call.
@@ -218,6 +283,13 @@ hasher_to_fcn!(
     LoseLoseHasher
 );
 
+finalized_hasher!(
+    /// `LoseLoseHasher` through `fmix64`; see [`Finalized`](crate::mixers::Finalized).
+    LoseLoseFinalized,
+    LoseLoseHasher,
+    loselose_finalized
+);
+
 // ------------------------------------
 
 #[cfg(test)]
@@ -0,0 +1,295 @@
+//! Pearson hashing, from Peter K. Pearson's *Fast Hashing of
+//! Variable-Length Text Strings*, CACM 33(6), June 1990.
+//!
+//! Pearson's design goals were those of a checksum: small changes to the
+//! input should produce large, random-looking changes to the output, and
+//! it should run fast on machines with nothing more than byte loads and
+//! exclusive-or.
+//!
+//! Each byte of input is combined with the running 8-bit hash by a
+//! lookup in a permutation of 0..=255: `h = T[h ^ byte]`. That makes
+//! `Pearson8` a reasonable checksum for tiny protocols, and a poor
+//! `HashMap` hash, since it only produces 256 distinct values.
+//! `Pearson64` is the usual widening: eight independent lanes, each with
+//! its own table, concatenated into a 64-bit result.
+//!
+//! The tables are permutations generated at compile time by a
+//! Fisher-Yates shuffle driven by [`SplitMix64`], so any seed gives a
+//! valid table. Hand-made tables can be supplied with `with_table` and
+//! `with_tables`.
+
+use crate::mixers::SplitMix64;
+
+/// The seed used for [`DEFAULT_TABLE`] and [`DEFAULT_TABLES`].
+pub const DEFAULT_SEED: u64 = 0;
+
+/// The table used by `Pearson8::default()`.
+pub const DEFAULT_TABLE: [u8; 256] = permutation_table(DEFAULT_SEED);
+
+/// The tables used by `Pearson64::default()`.
+pub const DEFAULT_TABLES: [[u8; 256]; 8] = permutation_tables(DEFAULT_SEED);
+
+/// A uniformly-chosen value in `0..bound`, from a 64-bit random word.
+///
+/// Lemire's multiply-shift reduction; the bias is at most bound / 2^64.
+#[inline(always)]
+const fn bounded(r: u64, bound: usize) -> usize {
+    ((r as u128 * bound as u128) >> 64) as usize
+}
+
+/// Generate `N` pseudorandom permutations of 0..=255 from `seed`.
+pub const fn permutation_tables<const N: usize>(seed: u64) -> [[u8; 256]; N] {
+    let mut rng = SplitMix64::new(seed);
+    let mut tables = [[0u8; 256]; N];
+    let mut t = 0;
+    while t < N {
+        let mut i = 0;
+        while i < 256 {
+            tables[t][i] = i as u8;
+            i += 1;
+        }
+        // Fisher-Yates, from the top down.
+        let mut i = 255;
+        while i > 0 {
+            let j = bounded(rng.next(), i + 1);
+            let tmp = tables[t][i];
+            tables[t][i] = tables[t][j];
+            tables[t][j] = tmp;
+            i -= 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+/// Generate a pseudorandom permutation of 0..=255 from `seed`.
+pub const fn permutation_table(seed: u64) -> [u8; 256] {
+    permutation_tables::<1>(seed)[0]
+}
+
+/// Check that `table` contains each byte exactly once.
+pub const fn is_permutation(table: &[u8; 256]) -> bool {
+    let mut seen = [false; 256];
+    let mut i = 0;
+    while i < 256 {
+        if seen[table[i] as usize] {
+            return false;
+        }
+        seen[table[i] as usize] = true;
+        i += 1;
+    }
+    true
+}
+
+// ====================================
+// Pearson8
+
+/// The original 8-bit Pearson hash.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Pearson8 {
+    pub table: &'static [u8; 256],
+    pub hash: u8,
+}
+
+impl Pearson8 {
+    #[inline(always)]
+    pub const fn default() -> Pearson8 {
+        Pearson8::with_table(&DEFAULT_TABLE)
+    }
+
+    /// The low byte of the seed is the initial hash; the other 56 bits are
+    /// ignored, so there are only 256 distinct seeded Hashers.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> Pearson8 {
+        let mut h = Pearson8::default();
+        h.hash = seed as u8;
+        h
+    }
+
+    /// Create a Pearson8 using a specific table, which should be a
+    /// permutation of 0..=255 (see [`is_permutation`]).
+    #[inline(always)]
+    pub const fn with_table(table: &'static [u8; 256]) -> Pearson8 {
+        debug_assert!(is_permutation(table));
+        Pearson8 { table, hash: 0 }
+    }
+
+    #[inline(always)]
+    pub const fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+
+    #[inline(always)]
+    pub const fn write(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            self.hash = self.table[(self.hash ^ bytes[i]) as usize];
+            i += 1;
+        }
+    }
+}
+
+duplicate_const_traits!(Pearson8);
+seedable_hasher!(Pearson8);
+hasher_to_fcn!(
+    /// Provide access to Pearson8 in a single call.
+    pearson8,
+    Pearson8
+);
+
+// ------------------------------------
+
+#[cfg(test)]
+mod pearson8_tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        assert!(is_permutation(&DEFAULT_TABLE));
+        assert!(DEFAULT_TABLES.iter().all(is_permutation));
+        assert_eq!(DEFAULT_TABLE, DEFAULT_TABLES[0]);
+        assert_ne!(DEFAULT_TABLES[0], DEFAULT_TABLES[1]);
+        assert_ne!(permutation_table(1), DEFAULT_TABLE);
+        let mut not_perm = DEFAULT_TABLE;
+        not_perm[0] = not_perm[1];
+        assert!(!is_permutation(&not_perm));
+    }
+
+    #[test]
+    fn basic() {
+        assert_eq!(pearson8(b""), 0);
+        assert_eq!(pearson8(b"a"), DEFAULT_TABLE[b'a' as usize] as u64);
+        let ab = DEFAULT_TABLE[(DEFAULT_TABLE[b'a' as usize] ^ b'b') as usize];
+        assert_eq!(pearson8(b"ab"), ab as u64);
+    }
+
+    #[test]
+    fn user_table() {
+        // With the identity permutation, Pearson hashing is xor.
+        static IDENTITY: [u8; 256] = {
+            let mut t = [0u8; 256];
+            let mut i = 0;
+            while i < 256 {
+                t[i] = i as u8;
+                i += 1;
+            }
+            t
+        };
+        let mut h = Pearson8::with_table(&IDENTITY);
+        h.write(&[0x0f, 0xf0, 0x01]);
+        assert_eq!(h.finish(), 0xfe);
+    }
+}
+
+// ====================================
+// Pearson64
+
+/// Eight lanes of Pearson hashing, each with its own table.
+///
+/// Lane `i` produces byte `i` (little-endian) of the result.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Pearson64 {
+    pub tables: &'static [[u8; 256]; 8],
+    pub hash: [u8; 8],
+}
+
+impl Pearson64 {
+    #[inline(always)]
+    pub const fn default() -> Pearson64 {
+        Pearson64::with_tables(&DEFAULT_TABLES)
+    }
+
+    /// Byte `i` (little-endian) of the seed is the initial hash of lane
+    /// `i`.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> Pearson64 {
+        let mut h = Pearson64::default();
+        h.hash = seed.to_le_bytes();
+        h
+    }
+
+    /// Create a Pearson64 using specific tables, each of which should be
+    /// a permutation of 0..=255 (see [`is_permutation`]). They should
+    /// also differ from one another, or the lanes will produce identical
+    /// bytes.
+    #[inline(always)]
+    pub const fn with_tables(tables: &'static [[u8; 256]; 8]) -> Pearson64 {
+        let mut i = 0;
+        while i < 8 {
+            debug_assert!(is_permutation(&tables[i]));
+            i += 1;
+        }
+        Pearson64 {
+            tables,
+            hash: [0; 8],
+        }
+    }
+
+    #[inline(always)]
+    pub const fn finish(&self) -> u64 {
+        u64::from_le_bytes(self.hash)
+    }
+
+    #[inline(always)]
+    pub const fn write(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            let mut lane = 0;
+            while lane < 8 {
+                self.hash[lane] = self.tables[lane][(self.hash[lane] ^ bytes[i]) as usize];
+                lane += 1;
+            }
+            i += 1;
+        }
+    }
+}
+
+duplicate_const_traits!(Pearson64);
+seedable_hasher!(Pearson64);
+hasher_to_fcn!(
+    /// Provide access to Pearson64 in a single call.
+    pearson64,
+    Pearson64
+);
+
+// ------------------------------------
+
+#[cfg(test)]
+mod pearson64_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_eq!(pearson64(b""), 0);
+        // The first lane is Pearson8 with the same table.
+        assert_eq!(pearson64(b"a") & 0xff, pearson8(b"a"));
+        assert_eq!(pearson64(b"abcdefg") & 0xff, pearson8(b"abcdefg"));
+        assert_ne!(pearson64(b"a"), pearson64(b"b"));
+    }
+
+    #[test]
+    fn streaming() {
+        let mut h = Pearson64::default();
+        h.write(b"abc");
+        h.write(b"defg");
+        assert_eq!(h.finish(), pearson64(b"abcdefg"));
+    }
+
+    #[test]
+    fn const_eval() {
+        const H: u64 = pearson64(b"abcdefg");
+        assert_eq!(H, pearson64(b"abcdefg"));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn not_permutations() {
+        static TABLES: [[u8; 256]; 8] = {
+            let mut tables = DEFAULT_TABLES;
+            tables[7][0] = tables[7][1];
+            tables
+        };
+        Pearson64::with_tables(&TABLES);
+    }
+}
@@ -102,6 +102,12 @@ impl Bricolage {
         Bricolage(0)
     }
 
+    /// The seed is the initial state.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> Bricolage {
+        Bricolage(seed)
+    }
+
     #[inline(always)]
     pub const fn write(&mut self, bytes: &[u8]) {
         let mut i = 0;
@@ -121,6 +127,7 @@ impl Bricolage {
 const MAGIC: u64 = 173773926194192273u64;
 
 duplicate_const_traits!(Bricolage);
+seedable_hasher!(Bricolage);
 hasher_to_fcn!(
     /// Provide access to Bricolage in a single call.
     bricolage,
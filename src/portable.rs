@@ -0,0 +1,185 @@
+//! Platform-independent hash values.
+//!
+//! `core::hash::Hasher`'s default integer methods feed the integer's
+//! native-endian bytes to `write`, and `usize`, `isize` and the length
+//! prefixes of slices and strings are as wide as a pointer. So the same
+//! `#[derive(Hash)]` value hashes differently on a 32-bit target and a
+//! 64-bit one, or on a little-endian and a big-endian one, even with a
+//! Hasher that is itself portable.
+//!
+//! [`Portable<H>`] fixes the encoding: every integer is written to `H` as
+//! its little-endian bytes, and `usize` and `isize` (and so every length
+//! prefix) are widened to 64 bits first. Hashes computed through it can be
+//! persisted or sent between machines, as long as `H` itself does not
+//! depend on the platform. Portable also makes the sequence of `write`
+//! calls the same everywhere, which matters for Hashers such as
+//! `Lookup3Hasher` whose result depends on how the input is split between
+//! calls.
+//!
+//! One gap remains: std hashes slices of integers (`[u16]`, `Vec<u32>`,
+//! but not `[u8]` or `str`, which are bytes anyway) by passing their
+//! memory to `write` in one call, without going through the integer
+//! methods. Those bytes are native-endian, so they match only between
+//! targets of the same endianness. Their length prefixes are still
+//! widened.
+
+use core::hash::Hasher;
+
+use crate::SeedableHasher;
+
+/// A Hasher that writes integers to `H` in a fixed-width, little-endian
+/// encoding.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Default)]
+pub struct Portable<H>(pub H);
+
+impl<H: SeedableHasher> SeedableHasher for Portable<H> {
+    #[inline(always)]
+    fn with_seed(seed: u64) -> Portable<H> {
+        Portable(H::with_seed(seed))
+    }
+
+    #[inline(always)]
+    fn with_seed128(seed: u128) -> Portable<H> {
+        Portable(H::with_seed128(seed))
+    }
+}
+
+impl<H: Hasher> Hasher for Portable<H> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, i: u8) {
+        self.0.write(&[i]);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, i: u16) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, i: u32) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, i: u64) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, i: u128) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline(always)]
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    #[inline(always)]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline(always)]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline(always)]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline(always)]
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    /// Sign-extended to 64 bits, so that e.g. `-1isize` hashes the same
+    /// everywhere.
+    #[inline(always)]
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+}
+
+// ------------------------------------
+
+#[cfg(all(test, feature = "fnv"))]
+mod portable_tests {
+    use super::*;
+    use crate::fnv::{FNV1aHasher64, fnv1a64};
+    use core::hash::{BuildHasher, BuildHasherDefault, Hash};
+
+    // FNV-1a is byte-at-a-time, so splitting the input between calls to
+    // write does not change the hash.
+    const B: BuildHasherDefault<Portable<FNV1aHasher64>> = BuildHasherDefault::new();
+
+    fn bytes_hash(bytes: &[u8]) -> u64 {
+        fnv1a64(bytes)
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(B.hash_one(0x0102u16), bytes_hash(&[2, 1]));
+        assert_eq!(B.hash_one(0x01020304u32), bytes_hash(&[4, 3, 2, 1]));
+        assert_eq!(B.hash_one(7usize), B.hash_one(7u64));
+        assert_eq!(B.hash_one(-1isize), B.hash_one(-1i64));
+        assert_eq!(B.hash_one(-1isize), bytes_hash(&[0xff; 8]));
+    }
+
+    #[test]
+    fn derived() {
+        // The same data, with pointer-sized and fixed-width fields.
+        #[derive(Hash)]
+        struct Native {
+            id: usize,
+            offset: isize,
+            tags: Vec<u16>,
+        }
+        #[derive(Hash)]
+        struct Fixed {
+            id: u64,
+            offset: i64,
+            len: u64,
+            tag0: u16,
+            tag1: u16,
+        }
+        let native = Native {
+            id: 1,
+            offset: -2,
+            tags: vec![3, 4],
+        };
+        let fixed = Fixed {
+            id: 1,
+            offset: -2,
+            len: 2,
+            tag0: 3,
+            tag1: 4,
+        };
+        assert_eq!(B.hash_one(&native), B.hash_one(&fixed));
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&1u64.to_le_bytes());
+        expected.extend_from_slice(&(-2i64).to_le_bytes());
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(&[3, 0, 4, 0]);
+        let mut h = Portable(FNV1aHasher64::default());
+        native.hash(&mut h);
+        assert_eq!(h.finish(), bytes_hash(&expected));
+    }
+}
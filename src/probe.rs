@@ -0,0 +1,388 @@
+//! Hash table simulation: how long are the probes?
+//!
+//! The chi-squared test counts keys per bucket, but open-addressing hash
+//! tables care about how far a key lands from where its hash says it
+//! should go, and about which bits of the hash they use. This module
+//! inserts hashes into simulated tables of three layouts and measures the
+//! lookups:
+//!
+//! - [`Layout::LinearProbing`]: the low bits of the hash choose a slot, and
+//!   a key goes in the first empty slot from there on.
+//! - [`Layout::RobinHood`]: linear probing, but a key being inserted takes
+//!   the slot of any key closer to its own home slot, which then moves on.
+//! - [`Layout::SwissTable`]: the layout of hashbrown, which is std's
+//!   HashMap. The low bits choose a group of 16 slots, probing from group
+//!   to group in a triangular sequence, and the top 7 bits are stored in a
+//!   control byte per slot. A lookup compares keys only in slots whose
+//!   control byte matches.
+//!
+//! Each layout is simulated at several load factors, and compared with
+//! the same simulation of a random function. A hash whose top 7 bits
+//! never change, like the 32-bit hashes returned as u64s, makes every
+//! SwissTable lookup compare every key it passes.
+
+use core::fmt;
+
+use crate::mixers::SplitMix64;
+
+/// A hash table layout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Layout {
+    LinearProbing,
+    RobinHood,
+    SwissTable,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 3] = [Layout::LinearProbing, Layout::RobinHood, Layout::SwissTable];
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Layout::LinearProbing => "linear probing",
+            Layout::RobinHood => "Robin Hood",
+            Layout::SwissTable => "SwissTable",
+        })
+    }
+}
+
+/// SwissTable's group size, for SSE2.
+const GROUP: usize = 16;
+
+/// Successful lookups in one table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProbeStats {
+    pub layout: Layout,
+    /// The number of entries divided by the capacity.
+    pub load_factor: f64,
+    /// The number of slots.
+    pub capacity: usize,
+    /// The number of keys found after probing k slots, or k groups for
+    /// SwissTable, at index k.
+    pub histogram: Vec<u64>,
+    /// The mean number of keys compared per lookup: every slot probed, or
+    /// for SwissTable every slot whose control byte matches.
+    pub comparisons: f64,
+}
+
+impl ProbeStats {
+    pub fn entries(&self) -> u64 {
+        self.histogram.iter().sum()
+    }
+
+    /// The mean probe length.
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(k, &n)| k as u64 * n)
+            .sum();
+        total as f64 / self.entries() as f64
+    }
+
+    /// The longest probe.
+    pub fn max(&self) -> usize {
+        self.histogram.len() - 1
+    }
+}
+
+impl fmt::Display for ProbeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<14} load {:.3}: mean probe {:.3}, max {}, {:.3} comparisons",
+            self.layout.to_string(),
+            self.load_factor,
+            self.mean(),
+            self.max(),
+            self.comparisons
+        )
+    }
+}
+
+/// Count `lengths` into a histogram.
+fn histogram(lengths: impl Iterator<Item = usize>) -> Vec<u64> {
+    let mut histogram = Vec::new();
+    for length in lengths {
+        if histogram.len() <= length {
+            histogram.resize(length + 1, 0);
+        }
+        histogram[length] += 1;
+    }
+    histogram
+}
+
+fn stats(layout: Layout, capacity: usize, lengths: Vec<usize>, comparisons: usize) -> ProbeStats {
+    ProbeStats {
+        layout,
+        load_factor: lengths.len() as f64 / capacity as f64,
+        capacity,
+        comparisons: comparisons as f64 / lengths.len() as f64,
+        histogram: histogram(lengths.into_iter()),
+    }
+}
+
+/// Insert `hashes` into a table of `capacity` slots, a power of two
+/// larger than `hashes.len()`, and look each of them up.
+pub fn simulate(layout: Layout, hashes: &[u64], capacity: usize) -> ProbeStats {
+    assert!(capacity.is_power_of_two() && capacity >= GROUP && hashes.len() < capacity);
+    let mask = capacity - 1;
+    match layout {
+        Layout::LinearProbing => {
+            let mut full = vec![false; capacity];
+            let lengths: Vec<usize> = hashes
+                .iter()
+                .map(|&h| {
+                    let mut pos = h as usize & mask;
+                    let mut length = 1;
+                    while full[pos] {
+                        pos = (pos + 1) & mask;
+                        length += 1;
+                    }
+                    full[pos] = true;
+                    length
+                })
+                .collect();
+            let comparisons = lengths.iter().sum();
+            stats(layout, capacity, lengths, comparisons)
+        }
+        Layout::RobinHood => {
+            // each slot holds the distance of its key from its home slot
+            let mut slots: Vec<Option<usize>> = vec![None; capacity];
+            for &h in hashes {
+                let mut pos = h as usize & mask;
+                let mut distance = 0;
+                while let Some(occupant) = slots[pos] {
+                    if occupant < distance {
+                        slots[pos] = Some(distance);
+                        distance = occupant;
+                    }
+                    pos = (pos + 1) & mask;
+                    distance += 1;
+                }
+                slots[pos] = Some(distance);
+            }
+            let lengths: Vec<usize> = slots.iter().flatten().map(|d| d + 1).collect();
+            let comparisons = lengths.iter().sum();
+            stats(layout, capacity, lengths, comparisons)
+        }
+        Layout::SwissTable => {
+            // each slot holds its key's index and control byte
+            let mut slots: Vec<Option<(usize, u8)>> = vec![None; capacity];
+            let probe = |h: u64| {
+                let mut pos = h as usize & mask;
+                let mut stride = 0;
+                core::iter::from_fn(move || {
+                    let group = pos;
+                    stride += GROUP;
+                    pos = (pos + stride) & mask;
+                    Some((0..GROUP).map(move |i| (group + i) & mask))
+                })
+            };
+            for (key, &h) in hashes.iter().enumerate() {
+                let slot = probe(h).flatten().find(|&s| slots[s].is_none()).unwrap();
+                slots[slot] = Some((key, (h >> 57) as u8));
+            }
+            let mut comparisons = 0;
+            let lengths: Vec<usize> = hashes
+                .iter()
+                .enumerate()
+                .map(|(key, &h)| {
+                    let tag = (h >> 57) as u8;
+                    for (length, group) in probe(h).enumerate() {
+                        for s in group {
+                            if let Some((k, t)) = slots[s]
+                                && t == tag
+                            {
+                                comparisons += 1;
+                                if k == key {
+                                    return length + 1;
+                                }
+                            }
+                        }
+                    }
+                    unreachable!()
+                })
+                .collect();
+            stats(layout, capacity, lengths, comparisons)
+        }
+    }
+}
+
+// ====================================
+// Simulation
+
+/// Every layout at several load factors, for one set of hashes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    /// The bits that are the same in every hash.
+    pub constant_bits: u64,
+    /// The results for the hashes.
+    pub results: Vec<ProbeStats>,
+    /// The results for a random function, in the same order.
+    pub random: Vec<ProbeStats>,
+}
+
+impl Simulation {
+    /// Simulate every layout at each load factor, in the largest table
+    /// that `hashes` can fill to it, using the first hashes.
+    pub fn new(hashes: &[u64], load_factors: &[f64]) -> Simulation {
+        let mut rng = SplitMix64::new(0);
+        let random: Vec<u64> = hashes.iter().map(|_| rng.next()).collect();
+        let mut results = Vec::new();
+        let mut random_results = Vec::new();
+        for &load_factor in load_factors {
+            assert!(load_factor > 0.0 && load_factor < 1.0);
+            let capacity = (hashes.len() as f64 / load_factor) as usize;
+            let capacity = if capacity.is_power_of_two() {
+                capacity
+            } else {
+                capacity.next_power_of_two() / 2
+            };
+            assert!(capacity >= GROUP, "too few hashes");
+            let entries = (capacity as f64 * load_factor) as usize;
+            for layout in Layout::ALL {
+                results.push(simulate(layout, &hashes[..entries], capacity));
+                random_results.push(simulate(layout, &random[..entries], capacity));
+            }
+        }
+        let all = hashes.iter().fold(u64::MAX, |a, &h| a & h);
+        let any = hashes.iter().fold(0, |a, &h| a | h);
+        Simulation {
+            constant_bits: !(any & !all),
+            results,
+            random: random_results,
+        }
+    }
+
+    /// Hash `keys` and simulate at each load factor.
+    pub fn of<F, K>(hash: F, keys: &[K], load_factors: &[f64]) -> Simulation
+    where
+        F: Fn(&[u8]) -> u64,
+        K: AsRef<[u8]>,
+    {
+        let hashes: Vec<u64> = keys.iter().map(|k| hash(k.as_ref())).collect();
+        Simulation::new(&hashes, load_factors)
+    }
+
+    /// Problems: constant high bits, and results at least 1.5 times as bad
+    /// as a random function's.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.constant_bits >> 32 == u32::MAX as u64 {
+            warnings.push("the upper 32 bits never change".to_string());
+        } else if self.constant_bits >> 57 == 0x7f {
+            warnings.push("the top 7 bits, SwissTable's control byte, never change".to_string());
+        }
+        for (result, random) in self.results.iter().zip(&self.random) {
+            if result.mean() > 1.5 * random.mean() {
+                warnings.push(format!(
+                    "{} at load {:.3}: mean probe {:.3}, random {:.3}",
+                    result.layout,
+                    result.load_factor,
+                    result.mean(),
+                    random.mean()
+                ));
+            }
+            // the other layouts compare every key they probe
+            if result.layout == Layout::SwissTable && result.comparisons > 1.5 * random.comparisons
+            {
+                warnings.push(format!(
+                    "{} at load {:.3}: {:.3} comparisons per lookup, random {:.3}",
+                    result.layout, result.load_factor, result.comparisons, random.comparisons
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod probe_tests {
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        // Every key has the same home, and the keys form a chain.
+        let hashes = [5u64; 10];
+        let linear = simulate(Layout::LinearProbing, &hashes, 16);
+        assert_eq!(linear.histogram, [0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(linear.mean(), 5.5);
+        assert_eq!(linear.comparisons, 5.5);
+        assert_eq!(linear.load_factor, 10.0 / 16.0);
+        let robin = simulate(Layout::RobinHood, &hashes, 16);
+        assert_eq!(robin.histogram, linear.histogram);
+        // One group holds them all, but every lookup compares with
+        // every key before it.
+        let swiss = simulate(Layout::SwissTable, &hashes, 16);
+        assert_eq!(swiss.histogram, [0, 10]);
+        assert_eq!(swiss.comparisons, 5.5);
+        // Distinct tags, so one comparison each.
+        let hashes: Vec<u64> = (0..10).map(|i| (i << 57) | 5).collect();
+        let swiss = simulate(Layout::SwissTable, &hashes, 16);
+        assert_eq!(swiss.comparisons, 1.0);
+
+        // Robin Hood evens out probe lengths: 0, 1 and 2 fill slots 1 to
+        // 3 from home 1, then 3 and 4 want slot 0, and 4 takes slot 1
+        // from 0 and pushes it along.
+        let hashes = [1, 1, 1, 0, 0];
+        let linear = simulate(Layout::LinearProbing, &hashes, 16);
+        assert_eq!(linear.histogram, [0, 2, 1, 1, 0, 1]);
+        let robin = simulate(Layout::RobinHood, &hashes, 16);
+        assert_eq!(robin.histogram, [0, 1, 2, 1, 1]);
+        assert_eq!(robin.mean(), linear.mean());
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = SplitMix64::new(1);
+        let hashes: Vec<u64> = (0..20_000).map(|_| rng.next()).collect();
+        let simulation = Simulation::new(&hashes, &[0.5, 0.875]);
+        assert_eq!(simulation.results.len(), 6);
+        assert_eq!(simulation.constant_bits, 0);
+        assert!(simulation.warnings().is_empty());
+        let linear = &simulation.results[0];
+        assert_eq!((linear.capacity, linear.entries()), (32768, 16384));
+        // Knuth: 1/2 (1 + 1/(1 - α)) probes per successful lookup
+        assert!((linear.mean() - 1.5).abs() < 0.05);
+        let linear = &simulation.results[3];
+        assert_eq!((linear.capacity, linear.entries()), (16384, 14336));
+        assert!((linear.mean() - 4.5).abs() < 0.5);
+        assert!(simulation.results[4].max() < linear.max());
+    }
+
+    #[cfg(all(feature = "oz", feature = "jenkins", feature = "keysets"))]
+    #[test]
+    fn hashers() {
+        use crate::jenkins::spooky_hash::spooky;
+        use crate::oz::{djb2, djb2_finalized};
+
+        let keys = crate::keysets::identifiers(10_000, 6);
+        let simulation = Simulation::of(spooky, &keys, &[0.5, 0.75, 0.875]);
+        assert!(
+            simulation.warnings().is_empty(),
+            "{:?}",
+            simulation.warnings()
+        );
+        let simulation = Simulation::of(djb2, &keys, &[0.5, 0.75, 0.875]);
+        let warnings = simulation.warnings();
+        assert_eq!(warnings[0], "the upper 32 bits never change");
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.starts_with("SwissTable at load 0.875"))
+        );
+
+        // the same 32 bits, spread over 64
+        let simulation = Simulation::of(djb2_finalized, &keys, &[0.5, 0.75, 0.875]);
+        assert!(
+            simulation.warnings().is_empty(),
+            "{:?}",
+            simulation.warnings()
+        );
+    }
+}
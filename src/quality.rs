@@ -0,0 +1,544 @@
+//! Statistical tests of hash quality.
+//!
+//! Each test asks whether a set of hash values looks like a sample from
+//! the uniform distribution, and reports a statistic together with its
+//! p-value: the probability that truly uniform hashes would do at least
+//! this badly. Small p-values (say, below 0.01) mean the Hasher is
+//! distinguishable from random on those keys; large ones mean the test
+//! could not tell. A good Hasher still gets a p-value below 0.01 on one
+//! key set in a hundred.
+//!
+//! - [`chi2`] counts the hashes in 2^b buckets, by their low bits, as a
+//!   hash table would. See http://burtleburtle.net/bob/hash/hashfaq.html.
+//! - [`kolmogorov_smirnov`] and [`anderson_darling`] compare the
+//!   distribution of the whole hash value with the uniform distribution.
+//!   Anderson-Darling weighs the tails more heavily. See
+//!   https://www.itl.nist.gov/div898/handbook/eda/section3/eda35g.htm and
+//!   https://www.itl.nist.gov/div898/handbook/eda/section3/eda35e.htm.
+//!
+//! [`QualityReport`] runs all three on any `Fn(&[u8]) -> u64`.
+
+use core::f64::consts::PI;
+use core::fmt;
+
+// ====================================
+// Distributions
+
+/// The natural logarithm of the gamma function, by the Lanczos
+/// approximation (g = 7, n = 9), good to about 15 digits.
+pub fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // the reflection formula
+        (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + G + 0.5;
+        let mut a = COEFFICIENTS[0];
+        for (i, c) in COEFFICIENTS.iter().enumerate().skip(1) {
+            a += c / (x + i as f64);
+        }
+        0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+    }
+}
+
+const EPSILON: f64 = 1e-15;
+const TINY: f64 = 1e-300;
+
+/// The series for the regularized lower incomplete gamma function P(a,
+/// x), which converges quickly for x < a + 1 (Numerical Recipes, 6.2).
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut ap = a;
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    for _ in 0..10_000 {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// The continued fraction for the regularized upper incomplete gamma
+/// function Q(a, x), which converges quickly for x >= a + 1, by the
+/// modified Lentz's method (Numerical Recipes, 6.2).
+fn gamma_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..10_000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// The regularized lower incomplete gamma function P(a, x) = γ(a, x) /
+/// Γ(a).
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    debug_assert!(a > 0.0 && x >= 0.0);
+    if x <= 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x).min(1.0)
+    } else {
+        (1.0 - gamma_fraction(a, x)).max(0.0)
+    }
+}
+
+/// The regularized upper incomplete gamma function Q(a, x) = Γ(a, x) /
+/// Γ(a) = 1 - P(a, x).
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+    debug_assert!(a > 0.0 && x >= 0.0);
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        (1.0 - gamma_series(a, x)).max(0.0)
+    } else {
+        gamma_fraction(a, x).min(1.0)
+    }
+}
+
+/// The probability that a chi-squared variable with `dof` degrees of
+/// freedom is at least `x`.
+pub fn chi2_upper_tail(x: f64, dof: f64) -> f64 {
+    gamma_q(dof / 2.0, x / 2.0)
+}
+
+/// The probability that a standard normal variable is at least `z`.
+pub fn normal_upper_tail(z: f64) -> f64 {
+    // erfc(x) = Q(1/2, x²)
+    let q = gamma_q(0.5, z * z / 2.0) / 2.0;
+    if z >= 0.0 { q } else { 1.0 - q }
+}
+
+/// The probability that a Poisson variable with mean `lambda` is at
+/// least `k`.
+pub fn poisson_upper_tail(k: u64, lambda: f64) -> f64 {
+    if k == 0 {
+        1.0
+    } else if lambda <= 0.0 {
+        0.0
+    } else {
+        gamma_p(k as f64, lambda)
+    }
+}
+
+/// The expected number of collisions among `n` values drawn uniformly
+/// at random from 2^`bits`, counting each value after the first
+/// occurrence of a hash as one collision: n - m (1 - (1 - 1/m)^n), for m
+/// = 2^`bits`, which is about n² / 2m while that is small.
+pub fn expected_collisions(n: usize, bits: u32) -> f64 {
+    let m = 2f64.powi(bits as i32);
+    let n = n as f64;
+    n + m * (n * (-1.0 / m).ln_1p()).exp_m1()
+}
+
+/// The variance of the number of collisions counted by
+/// [`expected_collisions`], which is the variance of the number of empty
+/// buckets: m (m - 1) (1 - 2/m)^n + m (1 - 1/m)^n - m² (1 - 1/m)^2n. It is
+/// close to the expectation while that is small, and much less once most
+/// buckets are full.
+pub fn collision_variance(n: usize, bits: u32) -> f64 {
+    let m = 2f64.powi(bits as i32);
+    let n = n as f64;
+    // rearranged around expm1 and ln_1p, which keep the precision that
+    // the terms above lose by nearly cancelling
+    let empty = (n * (-1.0 / m).ln_1p()).exp();
+    m * empty * -(n * (-1.0 / (m - 1.0)).ln_1p()).exp_m1()
+        + m * m * empty * empty * (n * (-1.0 / ((m - 1.0) * (m - 1.0))).ln_1p()).exp_m1()
+}
+
+/// The probability that the Kolmogorov-Smirnov statistic of `n` uniform
+/// samples is at least `d`, by Stephens' approximation to its
+/// distribution (Numerical Recipes, 14.3).
+pub fn ks_upper_tail(d: f64, n: usize) -> f64 {
+    let sqrt_n = (n as f64).sqrt();
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * d;
+    // Q(λ) = 2 Σ (-1)^(j-1) exp(-2 j² λ²), which converges slowly for
+    // small λ, where Q is 1 anyway.
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let a2 = -2.0 * lambda * lambda;
+    let mut sign = 2.0;
+    let mut sum = 0.0;
+    let mut previous = 0.0;
+    for j in 1..=100 {
+        let term = sign * (a2 * (j * j) as f64).exp();
+        sum += term;
+        if term.abs() <= 0.001 * previous || term.abs() <= 1e-8 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous = term.abs();
+    }
+    1.0
+}
+
+/// The probability that the Anderson-Darling statistic of `n` uniform
+/// samples is at least `a2`, after G. and J. Marsaglia, "Evaluating the
+/// Anderson-Darling Distribution", Journal of Statistical Software 9(2),
+/// 2004. The result is good to about six digits.
+pub fn ad_upper_tail(a2: f64, n: usize) -> f64 {
+    // the limiting distribution, as n goes to infinity
+    let z = a2;
+    let x = if z <= 0.0 {
+        0.0
+    } else if z < 2.0 {
+        (-1.233_714_1 / z).exp() / z.sqrt()
+            * (2.00012
+                + (0.247_105
+                    - (0.064_982_1 - (0.034_796_2 - (0.011_672 - 0.001_686_91 * z) * z) * z) * z)
+                    * z)
+    } else {
+        (-(1.0776
+            - (2.30695 - (0.43424 - (0.082_433 - (0.008_056 - 0.000_314_6 * z) * z) * z) * z) * z)
+            .exp())
+        .exp()
+    };
+    // a correction for finite n
+    let n = n as f64;
+    let error = if x > 0.8 {
+        (-130.2137
+            + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) * x) * x) * x) * x)
+            / n
+    } else {
+        let c = 0.01265 + 0.1757 / n;
+        if x < c {
+            let t = x / c;
+            let t = t.sqrt() * (1.0 - t) * (49.0 * t - 102.0);
+            t * (0.0037 / (n * n) + 0.00078 / n + 0.00006) / n
+        } else {
+            let t = (x - c) / (0.8 - c);
+            let t = -0.000_226_33
+                + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * t) * t) * t) * t) * t;
+            t * (0.04213 + 0.01365 / n) / n
+        }
+    };
+    (1.0 - (x + error)).clamp(0.0, 1.0)
+}
+
+// ====================================
+// Tests
+
+/// The result of one test.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct TestResult {
+    /// The test statistic. Larger is worse.
+    pub statistic: f64,
+    /// The probability of a statistic at least this large from uniform
+    /// hashes.
+    pub p_value: f64,
+}
+
+impl TestResult {
+    /// Whether the hashes pass at significance level `alpha`, i.e. the
+    /// p-value is at least `alpha`.
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.4} (p = {:.4})", self.statistic, self.p_value)
+    }
+}
+
+/// Pearson's chi-squared test of the low `bucket_bits` bits of `hashes`,
+/// which is to say of their distribution across the buckets of a hash
+/// table of size 2^`bucket_bits`. There are 2^`bucket_bits` - 1 degrees of
+/// freedom.
+///
+/// The test is only reliable when the table holds at least about five
+/// hashes per bucket.
+pub fn chi2(hashes: &[u64], bucket_bits: u32) -> TestResult {
+    assert!(bucket_bits < 32, "too many buckets");
+    let n_buckets = 1usize << bucket_bits;
+    let mask = n_buckets as u64 - 1;
+    let mut buckets = vec![0u64; n_buckets];
+    for &h in hashes {
+        buckets[(h & mask) as usize] += 1;
+    }
+    let expected = hashes.len() as f64 / n_buckets as f64;
+    let statistic: f64 = buckets
+        .iter()
+        .map(|&count| {
+            let r = count as f64 - expected;
+            r * r / expected
+        })
+        .sum();
+    TestResult {
+        statistic,
+        p_value: chi2_upper_tail(statistic, (n_buckets - 1) as f64),
+    }
+}
+
+/// The number of bits of bucket index for a chi-squared test of `n`
+/// hashes of `bits` bits: as many as allow an average of at least 8
+/// hashes per bucket, up to 16, and no more than `bits`.
+pub fn bucket_bits(n: usize, bits: u32) -> u32 {
+    let per_bucket = (n / 8).max(2);
+    (usize::BITS - 1 - per_bucket.leading_zeros()).clamp(1, 16.min(bits))
+}
+
+/// `hashes` of `bits` bits as points in (0, 1), sorted. Each is placed in
+/// the middle of its interval, so none is exactly 0 or 1.
+fn uniform_points(hashes: &[u64], bits: u32) -> Vec<f64> {
+    assert!((1..=64).contains(&bits), "bits must be in 1..=64");
+    let mask = u64::MAX >> (64 - bits);
+    let scale = 2f64.powi(bits as i32);
+    let mut points: Vec<f64> = hashes
+        .iter()
+        .map(|&h| ((h & mask) as f64 + 0.5) / scale)
+        .collect();
+    points.sort_by(f64::total_cmp);
+    points
+}
+
+/// The Kolmogorov-Smirnov test of `hashes`, taken as `bits`-bit numbers,
+/// against the uniform distribution. The statistic is the largest
+/// distance between their empirical distribution function and the
+/// uniform one.
+pub fn kolmogorov_smirnov(hashes: &[u64], bits: u32) -> TestResult {
+    let points = uniform_points(hashes, bits);
+    let n = points.len() as f64;
+    let mut statistic = 0.0f64;
+    for (i, &u) in points.iter().enumerate() {
+        let below = i as f64 / n;
+        let above = (i + 1) as f64 / n;
+        statistic = statistic.max(above - u).max(u - below);
+    }
+    TestResult {
+        statistic,
+        p_value: ks_upper_tail(statistic, points.len()),
+    }
+}
+
+/// The Anderson-Darling test of `hashes`, taken as `bits`-bit numbers,
+/// against the uniform distribution.
+pub fn anderson_darling(hashes: &[u64], bits: u32) -> TestResult {
+    let points = uniform_points(hashes, bits);
+    let n = points.len();
+    let sum: f64 = (0..n)
+        .map(|i| (2 * i + 1) as f64 * (points[i].ln() + (1.0 - points[n - 1 - i]).ln()))
+        .sum();
+    let statistic = -(n as f64) - sum / n as f64;
+    TestResult {
+        statistic,
+        p_value: ad_upper_tail(statistic, n),
+    }
+}
+
+// ====================================
+// QualityReport
+
+/// The results of all three tests for one hash function on one set of
+/// keys.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct QualityReport {
+    /// The number of keys.
+    pub keys: usize,
+    /// The width of the hashes, in bits.
+    pub bits: u32,
+    /// The number of bits of bucket index in the chi-squared test.
+    pub bucket_bits: u32,
+    pub chi2: TestResult,
+    pub kolmogorov_smirnov: TestResult,
+    pub anderson_darling: TestResult,
+}
+
+impl QualityReport {
+    /// Hash `keys` with `hash`, whose results are `bits` wide, and test
+    /// the hashes.
+    ///
+    /// The chi-squared test uses [`bucket_bits`] of the hashes.
+    pub fn new<F, K>(hash: F, keys: &[K], bits: u32) -> QualityReport
+    where
+        F: Fn(&[u8]) -> u64,
+        K: AsRef<[u8]>,
+    {
+        let hashes: Vec<u64> = keys.iter().map(|k| hash(k.as_ref())).collect();
+        QualityReport::from_hashes(&hashes, bits)
+    }
+
+    /// Test hashes, `bits` wide, that have already been computed.
+    pub fn from_hashes(hashes: &[u64], bits: u32) -> QualityReport {
+        assert!(hashes.len() >= 16, "too few hashes to test");
+        let bucket_bits = bucket_bits(hashes.len(), bits);
+        QualityReport {
+            keys: hashes.len(),
+            bits,
+            bucket_bits,
+            chi2: chi2(hashes, bucket_bits),
+            kolmogorov_smirnov: kolmogorov_smirnov(hashes, bits),
+            anderson_darling: anderson_darling(hashes, bits),
+        }
+    }
+
+    /// Whether all three tests pass at significance level `alpha`.
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.chi2.passes(alpha)
+            && self.kolmogorov_smirnov.passes(alpha)
+            && self.anderson_darling.passes(alpha)
+    }
+}
+
+impl fmt::Display for QualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chi2 ({} buckets): {}, KS: {}, AD: {}",
+            1u64 << self.bucket_bits,
+            self.chi2,
+            self.kolmogorov_smirnov,
+            self.anderson_darling
+        )
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod quality_tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance
+    }
+
+    #[test]
+    fn distributions() {
+        // Γ(5) = 24, Γ(1/2) = √π
+        assert!(close(ln_gamma(5.0), 24f64.ln(), 1e-12));
+        assert!(close(ln_gamma(0.5), PI.sqrt().ln(), 1e-12));
+        // critical values from the usual tables
+        assert!(close(chi2_upper_tail(3.841, 1.0), 0.05, 1e-4));
+        assert!(close(chi2_upper_tail(6.635, 1.0), 0.01, 1e-4));
+        assert!(close(chi2_upper_tail(154.302, 127.0), 0.05, 1e-4));
+        assert!(close(chi2_upper_tail(127.0, 127.0), 0.483_311, 1e-6));
+        assert!(close(gamma_p(3.0, 2.0) + gamma_q(3.0, 2.0), 1.0, 1e-12));
+        assert!(close(normal_upper_tail(1.959_964), 0.025, 1e-6));
+        assert!(close(normal_upper_tail(-1.959_964), 0.975, 1e-6));
+        // P(X >= 1) = 1 - e^-λ, even when λ is tiny
+        assert!(close(poisson_upper_tail(1, 1e-12) / 1e-12, 1.0, 1e-9));
+        assert!(close(
+            poisson_upper_tail(3, 2.0),
+            1.0 - 5.0 * (-2f64).exp(),
+            1e-12
+        ));
+        assert!(close(expected_collisions(2, 1), 0.5, 1e-12));
+        assert!(close(
+            expected_collisions(1 << 16, 64),
+            2f64.powi(-33),
+            1e-15
+        ));
+        assert!(close(
+            expected_collisions(1 << 20, 8),
+            (1 << 20) as f64 - 256.0,
+            1e-6
+        ));
+        // 3 values from 4, by enumeration: 87/256
+        assert!(close(collision_variance(3, 2), 87.0 / 256.0, 1e-12));
+        let (n, bits) = (10_000_000, 39);
+        assert!(close(
+            collision_variance(n, bits) / expected_collisions(n, bits),
+            1.0,
+            1e-4
+        ));
+        assert!(collision_variance(1 << 24, 16) < 1e-6);
+        assert!(close(
+            ks_upper_tail(1.358 / 1000f64.sqrt(), 1000),
+            0.05,
+            2e-3
+        ));
+        assert!(close(
+            ks_upper_tail(1.628 / 1000f64.sqrt(), 1000),
+            0.01,
+            1e-3
+        ));
+        assert!(close(ad_upper_tail(2.492, 1000), 0.05, 1e-3));
+        assert!(close(ad_upper_tail(3.878, 1000), 0.01, 1e-3));
+        assert!(close(ad_upper_tail(1.933, 1000), 0.10, 1e-3));
+    }
+
+    #[test]
+    fn statistics() {
+        // Evenly spaced points are as uniform as points get.
+        let even: Vec<u64> = (0..1024u64).map(|i| i << 54).collect();
+        assert!(kolmogorov_smirnov(&even, 64).statistic < 0.001);
+        assert!(anderson_darling(&even, 64).p_value > 0.99);
+        assert_eq!(chi2(&even, 4).statistic, 1024.0 * 15.0);
+        // but all in bucket 0 of 16
+        assert!(chi2(&even, 4).p_value < 1e-9);
+        let spread: Vec<u64> = (0..1024u64).collect();
+        assert_eq!(chi2(&spread, 4).statistic, 0.0);
+        assert_eq!(chi2(&spread, 4).p_value, 1.0);
+        // and points crowded into the bottom half are not uniform
+        let low: Vec<u64> = (0..1024u64).map(|i| i << 53).collect();
+        assert!(close(kolmogorov_smirnov(&low, 64).statistic, 0.5, 0.001));
+        assert!(kolmogorov_smirnov(&low, 64).p_value < 1e-9);
+        assert!(anderson_darling(&low, 64).p_value < 1e-6);
+    }
+
+    #[cfg(feature = "mixers")]
+    #[test]
+    fn random_passes() {
+        use crate::mixers::SplitMix64;
+
+        let mut rng = SplitMix64::new(2);
+        let hashes: Vec<u64> = (0..10_000).map(|_| rng.next()).collect();
+        let report = QualityReport::from_hashes(&hashes, 64);
+        assert_eq!(report.bucket_bits, 10);
+        assert!(report.passes(0.001), "{}", report);
+        let low: Vec<u64> = hashes.iter().map(|h| h & 0xffff_ffff).collect();
+        assert!(QualityReport::from_hashes(&low, 32).passes(0.001));
+        // 32-bit hashes are not uniform 64-bit ones
+        assert!(!QualityReport::from_hashes(&low, 64).passes(0.001));
+    }
+
+    #[cfg(all(feature = "oz", feature = "jenkins", feature = "keysets"))]
+    #[test]
+    fn hashers() {
+        use crate::jenkins::spooky_hash::spooky;
+        use crate::oz::loselose;
+
+        let keys = crate::keysets::identifiers(10_000, 6);
+        let report = QualityReport::new(spooky, &keys, 64);
+        assert!(report.passes(0.001), "{}", report);
+        let report = QualityReport::new(loselose, &keys, 64);
+        assert!(!report.chi2.passes(0.001));
+        assert!(!report.kolmogorov_smirnov.passes(0.001));
+        assert!(!report.anderson_darling.passes(0.001));
+    }
+}
@@ -0,0 +1,251 @@
+//! Randomly-seeded `BuildHasher`s, like std's `RandomState`.
+//!
+//! `BuildHasherDefault<H>` starts every Hasher from the same state, so
+//! every map in every process hashes the same way and an attacker who can
+//! choose keys can precompute collisions. The builders here start each
+//! Hasher from a seed instead:
+//!
+//! - once per process, a random seed is drawn from the operating system
+//!   (through std's `RandomState`);
+//! - each builder created with `new()` perturbs it with a counter, so two
+//!   maps in the same process hash differently too.
+//!
+//! [`RandomSeedState`] seeds any [`SeedableHasher`] with
+//! [`SeedableHasher::with_seed`], and the named builders below are aliases
+//! of it, so a builder's Hasher is the one `with_seed` gives for the same
+//! seed. `with_seed` gives the same builders with a fixed seed, for
+//! reproducible runs; `BuildHasherDefault` still works as before.
+//!
+//! A seed only helps if collisions depend on it. For Spooky, Lookup3 and
+//! Poly61 they do. For the simple multiplicative hashes (DJB2, SDBM, FNV,
+//! OAAT, Bricolage), the seed is just the initial state, and two inputs of
+//! the same length that collide from one state often collide from all of
+//! them; LoseLose sums its input, so its collisions never depend on the
+//! seed. None of these are a defense against a determined attacker.
+
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+use std::collections::hash_map::RandomState;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::SeedableHasher;
+use crate::mixers::{GOLDEN_GAMMA, splitmix64};
+
+/// The per-process random seed, drawn from the operating system on first
+/// use.
+pub fn process_seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| RandomState::new().hash_one(()))
+}
+
+/// A fresh seed: the process seed perturbed by a global counter, and
+/// mixed with SplitMix64 so that consecutive seeds are unrelated.
+pub fn random_seed() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    splitmix64(process_seed().wrapping_add(n.wrapping_mul(GOLDEN_GAMMA)))
+}
+
+/// A `BuildHasher` that creates `H`s with [`SeedableHasher::with_seed`].
+pub struct RandomSeedState<H> {
+    seed: u64,
+    hasher: PhantomData<fn() -> H>,
+}
+
+impl<H> RandomSeedState<H> {
+    /// A builder with a fresh random seed.
+    #[inline]
+    pub fn new() -> RandomSeedState<H> {
+        RandomSeedState::with_seed(random_seed())
+    }
+
+    /// A builder with a fixed seed.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> RandomSeedState<H> {
+        RandomSeedState {
+            seed,
+            hasher: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl<H> Default for RandomSeedState<H> {
+    #[inline]
+    fn default() -> RandomSeedState<H> {
+        RandomSeedState::new()
+    }
+}
+
+impl<H: SeedableHasher> BuildHasher for RandomSeedState<H> {
+    type Hasher = H;
+
+    #[inline]
+    fn build_hasher(&self) -> H {
+        H::with_seed(self.seed)
+    }
+}
+
+// Implemented by hand, as derives would require the same of `H`.
+
+impl<H> Clone for RandomSeedState<H> {
+    #[inline]
+    fn clone(&self) -> RandomSeedState<H> {
+        *self
+    }
+}
+
+impl<H> Copy for RandomSeedState<H> {}
+
+impl<H> PartialEq for RandomSeedState<H> {
+    #[inline]
+    fn eq(&self, other: &RandomSeedState<H>) -> bool {
+        self.seed == other.seed
+    }
+}
+
+impl<H> Eq for RandomSeedState<H> {}
+
+impl<H> PartialOrd for RandomSeedState<H> {
+    #[inline]
+    fn partial_cmp(&self, other: &RandomSeedState<H>) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<H> Ord for RandomSeedState<H> {
+    #[inline]
+    fn cmp(&self, other: &RandomSeedState<H>) -> core::cmp::Ordering {
+        self.seed.cmp(&other.seed)
+    }
+}
+
+impl<H> core::hash::Hash for RandomSeedState<H> {
+    #[inline]
+    fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+        self.seed.hash(state);
+    }
+}
+
+impl<H> core::fmt::Debug for RandomSeedState<H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RandomSeedState")
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+/// Builds `OAATHasher`s starting from a random state.
+#[cfg(feature = "jenkins")]
+pub type RandomOAATState = RandomSeedState<crate::jenkins::OAATHasher>;
+
+/// Builds `Lookup3Hasher`s with random `pc` and `pb`.
+#[cfg(feature = "jenkins")]
+pub type RandomLookup3State = RandomSeedState<crate::jenkins::Lookup3Hasher>;
+
+/// Builds `SpookyHasher`s with a random 64-bit seed, used for both halves
+/// of Spooky's 128-bit seed.
+#[cfg(feature = "jenkins")]
+pub type RandomSpookyState = RandomSeedState<crate::jenkins::spooky_hash::SpookyHasher>;
+
+/// Builds `DJB2Hasher`s starting from a random state.
+#[cfg(feature = "oz")]
+pub type RandomDJB2State = RandomSeedState<crate::oz::DJB2Hasher>;
+
+/// Builds `SDBMHasher`s starting from a random state.
+#[cfg(feature = "oz")]
+pub type RandomSDBMState = RandomSeedState<crate::oz::SDBMHasher>;
+
+/// Builds `LoseLoseHasher`s starting from a random state.
+#[cfg(feature = "oz")]
+pub type RandomLoseLoseState = RandomSeedState<crate::oz::LoseLoseHasher>;
+
+/// Builds `Bricolage` Hashers starting from a random state.
+#[cfg(feature = "pigeon")]
+pub type RandomBricolageState = RandomSeedState<crate::pigeon::Bricolage>;
+
+/// Builds `FNV1aHasher32`s starting from a random state.
+#[cfg(feature = "fnv")]
+pub type RandomFNV1a32State = RandomSeedState<crate::fnv::FNV1aHasher32>;
+
+/// Builds `FNV1aHasher64`s starting from a random state.
+#[cfg(feature = "fnv")]
+pub type RandomFNV1a64State = RandomSeedState<crate::fnv::FNV1aHasher64>;
+
+/// Builds `Pearson8` Hashers with a random initial hash.
+#[cfg(feature = "pearson")]
+pub type RandomPearson8State = RandomSeedState<crate::pearson::Pearson8>;
+
+/// Builds `Pearson64` Hashers with random initial hashes.
+#[cfg(feature = "pearson")]
+pub type RandomPearson64State = RandomSeedState<crate::pearson::Pearson64>;
+
+/// Builds `TabulationHasher`s with a random initial hash; the tables are
+/// fixed.
+#[cfg(feature = "tabulation")]
+pub type RandomTabulationState = RandomSeedState<crate::tabulation::TabulationHasher>;
+
+/// Builds `TwistedTabulationHasher`s with a random initial hash; the
+/// tables are fixed.
+#[cfg(feature = "tabulation")]
+pub type RandomTwistedTabulationState = RandomSeedState<crate::tabulation::TwistedTabulationHasher>;
+
+/// Builds `Poly61` Hashers with a random key.
+#[cfg(feature = "universal")]
+pub type RandomPoly61State = RandomSeedState<crate::universal::Poly61>;
+
+// ------------------------------------
+
+#[cfg(all(test, feature = "jenkins", feature = "fnv"))]
+mod random_tests {
+    use super::*;
+    use crate::jenkins::Lookup3Hasher;
+    use crate::jenkins::spooky_hash::SpookyHasher;
+    use core::hash::BuildHasherDefault;
+
+    #[test]
+    fn seeds() {
+        assert_eq!(process_seed(), process_seed());
+        assert_ne!(random_seed(), random_seed());
+        assert_ne!(RandomSpookyState::new(), RandomSpookyState::new());
+        assert_eq!(RandomSpookyState::with_seed(7).seed(), 7);
+        assert_eq!(RandomSeedState::<Lookup3Hasher>::with_seed(7).seed(), 7);
+    }
+
+    #[test]
+    fn build() {
+        let a = RandomSpookyState::new();
+        let b = RandomSpookyState::new();
+        assert_eq!(a.hash_one("abc"), a.hash_one("abc"));
+        assert_ne!(a.hash_one("abc"), b.hash_one("abc"));
+        let fixed = RandomLookup3State::with_seed(0);
+        assert_eq!(
+            fixed.hash_one(1u64),
+            BuildHasherDefault::<Lookup3Hasher>::default().hash_one(1u64)
+        );
+        assert_eq!(
+            RandomSpookyState::with_seed(7).build_hasher(),
+            SpookyHasher::with_seed(7)
+        );
+        assert_eq!(
+            RandomFNV1a64State::with_seed(5).build_hasher(),
+            crate::fnv::FNV1aHasher64::with_seed(5)
+        );
+    }
+
+    #[test]
+    fn hashmap() {
+        use std::collections::HashMap;
+
+        let mut map: HashMap<&str, u32, RandomFNV1a64State> = HashMap::default();
+        map.insert("one", 1);
+        map.insert("two", 2);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get("three"), None);
+    }
+}
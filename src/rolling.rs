@@ -0,0 +1,465 @@
+//! Rolling hashes.
+//!
+//! The other Hashers in this crate can only append: there is no way to
+//! take a byte back out of `FNV1aHasher64` or `DJB2Hasher`. A rolling
+//! hash keeps the hash of a fixed-size window of the input and can slide
+//! that window along by one byte in constant time, dropping the oldest
+//! byte and adding a new one. That is what substring search (Rabin-Karp)
+//! and content-defined chunking need.
+//!
+//! - [`RabinKarp`]: the polynomial hash `b_1 B^(w-1) + ... + b_w` modulo
+//!   2^61 - 1, from Karp and Rabin's *Efficient randomized
+//!   pattern-matching algorithms* (1987). For a random base, two distinct
+//!   windows collide with probability at most `w / (2^61 - 1)`.
+//! - [`Buzhash`]: cyclic polynomial hashing, `rot(T[b_1], w-1) ^ ... ^
+//!   T[b_w]`, from Uzgalis' BuzHash and Cohen's *Recursive Hashing
+//!   Functions for n-Grams* (1997). No multiplications.
+//! - [`Gear`]: `h = (h << 1) + G[b]`, from Xia et al.'s Ddelta (2014).
+//!   The window is implicit: after 64 shifts a byte has left the hash
+//!   entirely. The cheapest of the three, and the one FastCDC uses.
+//!
+//! The byte tables for Buzhash and Gear are generated at compile time
+//! with [`SplitMix64`].
+
+use crate::mixers::SplitMix64;
+use crate::universal::{P61, mul_add61, pow61};
+
+/// The seed used for the default tables and bases in this module.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Generate a table of 256 pseudorandom words from `seed`.
+pub const fn byte_table(seed: u64) -> [u64; 256] {
+    let mut rng = SplitMix64::new(seed);
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = rng.next();
+        i += 1;
+    }
+    table
+}
+
+/// A hash over a sliding window of bytes.
+pub trait RollingHasher {
+    /// The number of bytes in the window.
+    fn window(&self) -> usize;
+
+    /// Append a byte, growing the window. Used to fill the first window;
+    /// after `window()` pushes, use `roll`.
+    fn push(&mut self, in_byte: u8);
+
+    /// Slide the window along by one: remove `out_byte`, which must be the
+    /// oldest byte in the window, and append `in_byte`.
+    fn roll(&mut self, out_byte: u8, in_byte: u8);
+
+    /// The hash of the current window.
+    fn hash(&self) -> u64;
+
+    /// Return to the empty window.
+    fn reset(&mut self);
+}
+
+// ====================================
+// Rabin-Karp
+
+/// Polynomial rolling hash modulo 2^61 - 1.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct RabinKarp {
+    pub base: u64,
+    // base^(window - 1), the weight of the oldest byte
+    pub out_weight: u64,
+    pub window: usize,
+    pub hash: u64,
+}
+
+impl RabinKarp {
+    /// A window of `window` bytes, with a base derived from the default
+    /// seed.
+    #[inline(always)]
+    pub const fn new(window: usize) -> RabinKarp {
+        RabinKarp::from_seed(window, DEFAULT_SEED)
+    }
+
+    /// A window of `window` bytes, with a base derived from `seed`.
+    #[inline(always)]
+    pub const fn from_seed(window: usize, seed: u64) -> RabinKarp {
+        // Avoid tiny bases, where small windows trivially collide.
+        let base = 256 + SplitMix64::new(seed).next() % (P61 - 256);
+        RabinKarp::with_base(window, base)
+    }
+
+    /// A window of `window` bytes, with a specific base `< 2^61 - 1`.
+    #[inline(always)]
+    pub const fn with_base(window: usize, base: u64) -> RabinKarp {
+        assert!(window > 0);
+        debug_assert!(base < P61);
+        RabinKarp {
+            base,
+            out_weight: pow61(base, window as u64 - 1),
+            window,
+            hash: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn push(&mut self, in_byte: u8) {
+        self.hash = mul_add61(self.hash, self.base, in_byte as u64);
+    }
+
+    #[inline(always)]
+    pub const fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        let out = mul_add61(out_byte as u64, self.out_weight, 0);
+        let h = if self.hash >= out {
+            self.hash - out
+        } else {
+            self.hash + P61 - out
+        };
+        self.hash = mul_add61(h, self.base, in_byte as u64);
+    }
+}
+
+impl RollingHasher for RabinKarp {
+    #[inline(always)]
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    #[inline(always)]
+    fn push(&mut self, in_byte: u8) {
+        self.push(in_byte);
+    }
+
+    #[inline(always)]
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        self.roll(out_byte, in_byte);
+    }
+
+    #[inline(always)]
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.hash = 0;
+    }
+}
+
+// ====================================
+// Buzhash
+
+/// The table used by `Buzhash::new`.
+pub const BUZHASH_TABLE: [u64; 256] = byte_table(DEFAULT_SEED);
+
+/// Cyclic polynomial rolling hash.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Buzhash {
+    pub table: &'static [u64; 256],
+    pub window: usize,
+    pub hash: u64,
+}
+
+impl Buzhash {
+    /// A window of `window` bytes, using [`BUZHASH_TABLE`].
+    #[inline(always)]
+    pub const fn new(window: usize) -> Buzhash {
+        Buzhash::with_table(window, &BUZHASH_TABLE)
+    }
+
+    /// A window of `window` bytes, using a specific table.
+    #[inline(always)]
+    pub const fn with_table(window: usize, table: &'static [u64; 256]) -> Buzhash {
+        Buzhash {
+            table,
+            window,
+            hash: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn push(&mut self, in_byte: u8) {
+        self.hash = self.hash.rotate_left(1) ^ self.table[in_byte as usize];
+    }
+
+    #[inline(always)]
+    pub const fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        // The oldest byte has been rotated window - 1 times; after this
+        // roll's rotation, window times.
+        let out = self.table[out_byte as usize].rotate_left((self.window % 64) as u32);
+        self.hash = self.hash.rotate_left(1) ^ out ^ self.table[in_byte as usize];
+    }
+}
+
+impl RollingHasher for Buzhash {
+    #[inline(always)]
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    #[inline(always)]
+    fn push(&mut self, in_byte: u8) {
+        self.push(in_byte);
+    }
+
+    #[inline(always)]
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        self.roll(out_byte, in_byte);
+    }
+
+    #[inline(always)]
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.hash = 0;
+    }
+}
+
+// ====================================
+// Gear
+
+/// The table used by `Gear::new`.
+pub const GEAR_TABLE: [u64; 256] = byte_table(DEFAULT_SEED ^ 1);
+
+/// Gear rolling hash.
+///
+/// The hash proper always covers the last 64 bytes. For a shorter window
+/// of `w` bytes, `hash()` returns only the low `w` bits, which depend on
+/// nothing older; that makes short Gear windows weak, but correct.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Gear {
+    pub table: &'static [u64; 256],
+    pub window: usize,
+    pub hash: u64,
+}
+
+impl Gear {
+    /// A 64-byte window, using [`GEAR_TABLE`].
+    #[inline(always)]
+    pub const fn new() -> Gear {
+        Gear::with_table(64, &GEAR_TABLE)
+    }
+
+    /// A window of `window <= 64` bytes, using a specific table.
+    #[inline(always)]
+    pub const fn with_table(window: usize, table: &'static [u64; 256]) -> Gear {
+        assert!(window > 0 && window <= 64);
+        Gear {
+            table,
+            window,
+            hash: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn push(&mut self, in_byte: u8) {
+        self.hash = (self.hash << 1).wrapping_add(self.table[in_byte as usize]);
+    }
+
+    /// Gear forgets old bytes by shifting them out, so `out_byte` is not
+    /// needed.
+    #[inline(always)]
+    pub const fn roll(&mut self, _out_byte: u8, in_byte: u8) {
+        self.push(in_byte);
+    }
+
+    #[inline(always)]
+    pub const fn hash(&self) -> u64 {
+        if self.window >= 64 {
+            self.hash
+        } else {
+            self.hash & ((1 << self.window) - 1)
+        }
+    }
+}
+
+impl Default for Gear {
+    #[inline]
+    fn default() -> Gear {
+        Gear::new()
+    }
+}
+
+impl RollingHasher for Gear {
+    #[inline(always)]
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    #[inline(always)]
+    fn push(&mut self, in_byte: u8) {
+        self.push(in_byte);
+    }
+
+    #[inline(always)]
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        self.roll(out_byte, in_byte);
+    }
+
+    #[inline(always)]
+    fn hash(&self) -> u64 {
+        self.hash()
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.hash = 0;
+    }
+}
+
+// ====================================
+// Substring search
+
+/// An iterator over the starting positions of a needle in a haystack,
+/// found by comparing rolling hashes of each window with the hash of the
+/// needle. Candidates are checked byte-for-byte, so there are no false
+/// positives. Overlapping matches are all reported.
+#[derive(Clone, Debug)]
+pub struct FindAll<'a, R> {
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    roller: R,
+    target: u64,
+    // start of the window currently in `roller`
+    pos: usize,
+}
+
+impl<'a, R: RollingHasher + Clone> FindAll<'a, R> {
+    fn new(haystack: &'a [u8], needle: &'a [u8], mut roller: R) -> FindAll<'a, R> {
+        assert_eq!(roller.window(), needle.len());
+        roller.reset();
+        let mut needle_hash = roller.clone();
+        needle.iter().for_each(|&b| needle_hash.push(b));
+        if needle.len() <= haystack.len() {
+            haystack[..needle.len()]
+                .iter()
+                .for_each(|&b| roller.push(b));
+        }
+        FindAll {
+            haystack,
+            needle,
+            roller,
+            target: needle_hash.hash(),
+            pos: 0,
+        }
+    }
+}
+
+impl<R: RollingHasher> Iterator for FindAll<'_, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let n = self.needle.len();
+        if n == 0 {
+            return None;
+        }
+        while self.pos + n <= self.haystack.len() {
+            let start = self.pos;
+            let found = self.roller.hash() == self.target
+                && &self.haystack[start..start + n] == self.needle;
+            if start + n < self.haystack.len() {
+                self.roller
+                    .roll(self.haystack[start], self.haystack[start + n]);
+            }
+            self.pos += 1;
+            if found {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+/// Find every occurrence of `needle` in `haystack`, using [`RabinKarp`].
+///
+/// An empty needle matches nowhere.
+pub fn find_all<'a>(haystack: &'a [u8], needle: &'a [u8]) -> FindAll<'a, RabinKarp> {
+    if needle.is_empty() {
+        // A one-byte window over an empty haystack finds nothing.
+        return FindAll::new(&[], &[0], RabinKarp::new(1));
+    }
+    FindAll::new(haystack, needle, RabinKarp::new(needle.len()))
+}
+
+/// Find every occurrence of `needle` in `haystack`, using any rolling
+/// hash whose window is `needle.len()` bytes.
+///
+/// As with [`find_all`], an empty needle matches nowhere.
+pub fn find_all_with<'a, R: RollingHasher + Clone>(
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    roller: R,
+) -> FindAll<'a, R> {
+    FindAll::new(haystack, needle, roller)
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod rolling_tests {
+    use super::*;
+
+    const DATA: &[u8] = b"the quick brown fox jumps over the lazy dog, the quick brown cat";
+
+    /// Rolling across `DATA` must agree with hashing each window afresh.
+    fn check_rolls<R: RollingHasher + Clone>(fresh: R) {
+        let w = fresh.window();
+        let mut roller = fresh.clone();
+        DATA[..w].iter().for_each(|&b| roller.push(b));
+        for start in 0..=DATA.len() - w {
+            let mut expected = fresh.clone();
+            DATA[start..start + w]
+                .iter()
+                .for_each(|&b| expected.push(b));
+            assert_eq!(roller.hash(), expected.hash(), "window at {}", start);
+            if start + w < DATA.len() {
+                roller.roll(DATA[start], DATA[start + w]);
+            }
+        }
+    }
+
+    #[test]
+    fn rabin_karp() {
+        for w in [1, 2, 7, 16, 63] {
+            check_rolls(RabinKarp::new(w));
+        }
+        let mut rk = RabinKarp::with_base(3, 10);
+        b"123".iter().for_each(|&b| rk.push(b - b'0'));
+        assert_eq!(rk.hash, 123);
+        rk.roll(1, 4);
+        assert_eq!(rk.hash, 234);
+    }
+
+    #[test]
+    fn buzhash() {
+        for w in [1, 2, 7, 16, 63, 64] {
+            check_rolls(Buzhash::new(w));
+        }
+    }
+
+    #[test]
+    fn gear() {
+        check_rolls(Gear::new());
+        for w in [1, 7, 32] {
+            check_rolls(Gear::with_table(w, &GEAR_TABLE));
+        }
+        assert_ne!(GEAR_TABLE, BUZHASH_TABLE);
+    }
+
+    #[test]
+    fn find() {
+        let found: Vec<usize> = find_all(DATA, b"the").collect();
+        assert_eq!(found, vec![0, 31, 45]);
+        let found: Vec<usize> = find_all(b"aaaa", b"aa").collect();
+        assert_eq!(found, vec![0, 1, 2]);
+        assert_eq!(find_all(DATA, b"zebra").count(), 0);
+        assert_eq!(find_all(b"ab", b"abc").count(), 0);
+        assert_eq!(find_all(DATA, b"").count(), 0);
+        assert_eq!(find_all_with(DATA, b"", Buzhash::new(0)).count(), 0);
+        assert_eq!(find_all(DATA, DATA).collect::<Vec<_>>(), vec![0]);
+        let found: Vec<usize> = find_all_with(DATA, b"quick", Buzhash::new(5)).collect();
+        assert_eq!(found, vec![4, 49]);
+    }
+}
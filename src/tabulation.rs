@@ -0,0 +1,359 @@
+//! Tabulation hashing.
+//!
+//! Simple tabulation, due to Zobrist (1970) and analyzed by Pătraşcu and
+//! Thorup in *The Power of Simple Tabulation Hashing* (2011), splits a key
+//! into `C` bytes and xors together one random table entry per byte:
+//!
+//! ```text
+//! h(x) = T[0][x_0] ^ T[1][x_1] ^ ... ^ T[C-1][x_{C-1}]
+//! ```
+//!
+//! It is only 3-independent, but Pătraşcu and Thorup showed that it
+//! behaves like a truly random function for linear probing, cuckoo
+//! hashing and min-wise hashing, with expected probe lengths within a
+//! constant of ideal. Twisted tabulation (Pătraşcu and Thorup, *Twisted
+//! Tabulation Hashing*, 2013) adds one more lookup's worth of work and
+//! gives Chernoff-style concentration bounds, which matter for sketches
+//! and for bounding the *worst* bucket rather than the average.
+//!
+//! The guarantees hold for the random choice of tables, so they are
+//! generated at compile time from a seed using [`SplitMix64`] and can
+//! live in `static` memory. Different seeds give independent functions.
+//!
+//! The byte-string Hashers here chain tabulation over 8-byte words and
+//! are *not* covered by those guarantees; for integer keys, use the
+//! functions directly.
+
+use crate::mixers::SplitMix64;
+
+/// The seed used for the tables in this module's statics.
+pub const DEFAULT_SEED: u64 = 0;
+
+// ====================================
+// Simple tabulation
+
+/// Simple tabulation over the low `C` bytes of a key, with 64-bit
+/// output. `C` is 1 to 8:
+///
+/// ```compile_fail
+/// use const_hashers::tabulation::SimpleTabulation;
+///
+/// static TOO_WIDE: SimpleTabulation<9> = SimpleTabulation::new(0);
+/// ```
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct SimpleTabulation<const C: usize> {
+    pub tables: [[u64; 256]; C],
+}
+
+impl<const C: usize> SimpleTabulation<C> {
+    /// A key has 1 to 8 bytes; other `C`s fail to compile.
+    const BYTES: () = assert!(1 <= C && C <= 8, "C must be 1 to 8 bytes");
+
+    /// Fill the tables from `seed`.
+    pub const fn new(seed: u64) -> SimpleTabulation<C> {
+        let () = Self::BYTES;
+        let mut rng = SplitMix64::new(seed);
+        let mut tables = [[0u64; 256]; C];
+        let mut i = 0;
+        while i < C {
+            let mut j = 0;
+            while j < 256 {
+                tables[i][j] = rng.next();
+                j += 1;
+            }
+            i += 1;
+        }
+        SimpleTabulation { tables }
+    }
+
+    /// Hash the low `C` bytes of `key`.
+    #[inline(always)]
+    pub const fn hash(&self, key: u64) -> u64 {
+        let () = Self::BYTES;
+        let mut h = 0;
+        let mut i = 0;
+        while i < C {
+            h ^= self.tables[i][((key >> (8 * i)) & 0xff) as usize];
+            i += 1;
+        }
+        h
+    }
+}
+
+/// Simple tabulation for 32-bit keys.
+pub static SIMPLE32: SimpleTabulation<4> = SimpleTabulation::new(DEFAULT_SEED);
+/// Simple tabulation for 64-bit keys.
+pub static SIMPLE64: SimpleTabulation<8> = SimpleTabulation::new(DEFAULT_SEED);
+
+/// Hash a `u32` with [`SIMPLE32`].
+#[inline(always)]
+pub const fn simple32(key: u32) -> u64 {
+    SIMPLE32.hash(key as u64)
+}
+
+/// Hash a `u64` with [`SIMPLE64`].
+#[inline(always)]
+pub const fn simple64(key: u64) -> u64 {
+    SIMPLE64.hash(key)
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod simple_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_eq!(simple64(0), SIMPLE64.tables.iter().fold(0, |h, t| h ^ t[0]));
+        // Same seed, same stream: the 32-bit tables are a prefix.
+        assert_eq!(SIMPLE32.tables, SIMPLE64.tables[..4]);
+        assert_ne!(simple64(1), simple64(2));
+        assert_ne!(SimpleTabulation::<8>::new(1), SIMPLE64);
+    }
+
+    #[test]
+    fn linear() {
+        // Simple tabulation is only 3-independent: any four keys forming a
+        // "rectangle" over two byte positions hash to values that xor to
+        // zero.
+        let (a0, a1, b0, b1) = (0x11u64, 0x22u64, 0x3300u64, 0x4400u64);
+        let x = simple64(a0 | b0) ^ simple64(a0 | b1) ^ simple64(a1 | b0) ^ simple64(a1 | b1);
+        assert_eq!(x, 0);
+    }
+}
+
+// ====================================
+// Twisted tabulation
+
+/// Twisted tabulation over the low `C` bytes of a key, with 64-bit
+/// output.
+///
+/// The first `C - 1` lookups also produce a "twist" byte, which is xored
+/// into the last key byte before its lookup. The last twist table is not
+/// used.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct TwistedTabulation<const C: usize> {
+    pub tables: [[u64; 256]; C],
+    pub twists: [[u8; 256]; C],
+}
+
+impl<const C: usize> TwistedTabulation<C> {
+    /// A key has 1 to 8 bytes; other `C`s fail to compile.
+    const BYTES: () = assert!(1 <= C && C <= 8, "C must be 1 to 8 bytes");
+
+    /// Fill the tables from `seed`.
+    pub const fn new(seed: u64) -> TwistedTabulation<C> {
+        let () = Self::BYTES;
+        let mut rng = SplitMix64::new(seed);
+        let mut tables = [[0u64; 256]; C];
+        let mut twists = [[0u8; 256]; C];
+        let mut i = 0;
+        while i < C {
+            let mut j = 0;
+            while j < 256 {
+                tables[i][j] = rng.next();
+                twists[i][j] = rng.next() as u8;
+                j += 1;
+            }
+            i += 1;
+        }
+        TwistedTabulation { tables, twists }
+    }
+
+    /// Hash the low `C` bytes of `key`.
+    #[inline(always)]
+    pub const fn hash(&self, key: u64) -> u64 {
+        let () = Self::BYTES;
+        let mut h = 0;
+        let mut twist = 0;
+        let mut i = 0;
+        while i + 1 < C {
+            let c = ((key >> (8 * i)) & 0xff) as usize;
+            h ^= self.tables[i][c];
+            twist ^= self.twists[i][c];
+            i += 1;
+        }
+        let last = ((key >> (8 * (C - 1))) & 0xff) as u8;
+        h ^ self.tables[C - 1][(last ^ twist) as usize]
+    }
+}
+
+/// Twisted tabulation for 32-bit keys.
+pub static TWISTED32: TwistedTabulation<4> = TwistedTabulation::new(DEFAULT_SEED);
+/// Twisted tabulation for 64-bit keys.
+pub static TWISTED64: TwistedTabulation<8> = TwistedTabulation::new(DEFAULT_SEED);
+
+/// Hash a `u32` with [`TWISTED32`].
+#[inline(always)]
+pub const fn twisted32(key: u32) -> u64 {
+    TWISTED32.hash(key as u64)
+}
+
+/// Hash a `u64` with [`TWISTED64`].
+#[inline(always)]
+pub const fn twisted64(key: u64) -> u64 {
+    TWISTED64.hash(key)
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod twisted_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_ne!(twisted64(1), twisted64(2));
+        assert_ne!(twisted64(1), simple64(1));
+        assert_ne!(TwistedTabulation::<8>::new(1), TWISTED64);
+    }
+
+    #[test]
+    fn not_linear() {
+        // The rectangle that defeats simple tabulation, with the last key
+        // byte as one of the two positions.
+        let (a0, a1, b0, b1) = (0x11u64, 0x22u64, 0x33u64 << 56, 0x44u64 << 56);
+        let x = twisted64(a0 | b0) ^ twisted64(a0 | b1) ^ twisted64(a1 | b0) ^ twisted64(a1 | b1);
+        assert_ne!(x, 0);
+    }
+
+    #[test]
+    fn const_eval() {
+        const T: TwistedTabulation<4> = TwistedTabulation::new(DEFAULT_SEED);
+        const H: u64 = T.hash(0xdeadbeef);
+        assert_eq!(H, twisted32(0xdeadbeef));
+    }
+}
+
+// ====================================
+// Byte-string Hashers
+
+macro_rules! tabulation_hasher {
+    ($(#[$attr:meta])* $name:ident, $table_ty:ty, $table:expr) => {
+        $(#[$attr])*
+        #[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+        pub struct $name {
+            pub table: &'static $table_ty,
+            // hash of the complete words so far
+            pub hash: u64,
+            // bytes of an incomplete word, little-endian
+            pub tail: u64,
+            // total length of the input so far
+            pub length: u64,
+        }
+
+        impl $name {
+            #[inline(always)]
+            pub const fn default() -> $name {
+                $name::with_table(&$table)
+            }
+
+            /// The seed is the initial hash.
+            #[inline(always)]
+            pub const fn with_seed(seed: u64) -> $name {
+                let mut h = $name::default();
+                h.hash = seed;
+                h
+            }
+
+            /// Create a Hasher using a specific table.
+            #[inline(always)]
+            pub const fn with_table(table: &'static $table_ty) -> $name {
+                $name {
+                    table,
+                    hash: 0,
+                    tail: 0,
+                    length: 0,
+                }
+            }
+
+            #[inline(always)]
+            pub const fn finish(&self) -> u64 {
+                let h = self.table.hash(self.hash ^ self.tail);
+                self.table.hash(h ^ self.length)
+            }
+
+            #[inline]
+            pub const fn write(&mut self, bytes: &[u8]) {
+                let mut i = 0;
+                while i < bytes.len() {
+                    let used = (self.length % 8) as u32;
+                    self.tail |= (bytes[i] as u64) << (8 * used);
+                    self.length += 1;
+                    if used == 7 {
+                        self.hash = self.table.hash(self.hash ^ self.tail);
+                        self.tail = 0;
+                    }
+                    i += 1;
+                }
+            }
+        }
+
+        duplicate_const_traits!($name);
+        seedable_hasher!($name);
+    };
+}
+
+tabulation_hasher!(
+    /// A byte-string Hasher chaining [`SimpleTabulation`] over 8-byte
+    /// little-endian words, with the length folded in at the end.
+    TabulationHasher,
+    SimpleTabulation<8>,
+    SIMPLE64
+);
+
+tabulation_hasher!(
+    /// A byte-string Hasher chaining [`TwistedTabulation`] over 8-byte
+    /// little-endian words, with the length folded in at the end.
+    TwistedTabulationHasher,
+    TwistedTabulation<8>,
+    TWISTED64
+);
+
+hasher_to_fcn!(
+    /// Provide access to TabulationHasher in a single call.
+    tabulation,
+    TabulationHasher
+);
+
+hasher_to_fcn!(
+    /// Provide access to TwistedTabulationHasher in a single call.
+    twisted_tabulation,
+    TwistedTabulationHasher
+);
+
+// ------------------------------------
+
+#[cfg(test)]
+mod hasher_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_eq!(tabulation(b""), simple64(simple64(0)));
+        assert_ne!(tabulation(b"a"), tabulation(b"a\0"));
+        assert_ne!(twisted_tabulation(b"a"), twisted_tabulation(b"b"));
+    }
+
+    #[test]
+    fn streaming() {
+        let data = b"abcdefghijklmnopqrstuvwxyz";
+        let mut h = TwistedTabulationHasher::default();
+        h.write(&data[..3]);
+        h.write(&data[3..11]);
+        h.write(&data[11..]);
+        assert_eq!(h.finish(), twisted_tabulation(data));
+    }
+
+    #[test]
+    fn hashmap() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasherDefault;
+
+        let mut map: HashMap<u64, u64, BuildHasherDefault<TwistedTabulationHasher>> =
+            HashMap::default();
+        map.insert(1, 2);
+        assert_eq!(map.get(&1), Some(&2));
+    }
+}
@@ -0,0 +1,572 @@
+//! Universal hash families.
+//!
+//! Everything else in this crate is a fixed function: for any of them,
+//! someone who knows the function can choose keys that collide. A
+//! *universal family* is a set of functions indexed by a key, with a
+//! bound on the probability that two given, distinct inputs collide when
+//! the key is chosen at random. The bound holds for *every* pair of
+//! inputs, so worst-case behaviour can be reasoned about as long as the
+//! key is random and kept away from whoever picks the inputs.
+//!
+//! This module provides:
+//!
+//! - [`MultiplyShift`]: Dietzfelbinger et al.'s multiply-shift, for
+//!   64-bit integers. 2-approximately universal: for `x != y`,
+//!   `Pr[h(x) == h(y)] <= 2 / 2^bits`.
+//! - [`MultiplyAddShift`]: multiply-add-shift with 128-bit arithmetic.
+//!   Strongly universal (pairwise independent) on 64-bit integers: for
+//!   `x != y` and any outputs `u`, `v`, `Pr[h(x) == u && h(y) == v] ==
+//!   2^-2bits`.
+//! - [`Poly61`]: polynomial hashing of byte strings modulo the Mersenne
+//!   prime 2^61 - 1. For distinct strings of at most `n` bytes,
+//!   `Pr[collision] <= (ceil(n / 7) + 1) / (2^61 - 1)`.
+//! - [`Polymur`]: Orson Peters' Polymur-hash 2.0, a faster polynomial
+//!   hash over 2^61 - 1 with a keyed output mix. For distinct strings of
+//!   at most `n` bytes, `Pr[collision] <= n * 2^-60.2`.
+//!
+//! Keys can be given directly, or derived from a 64-bit seed with
+//! `from_seed`, which spreads the seed with [`SplitMix64`]. The bounds
+//! assume the key is uniformly random; a constant seed gives a fixed
+//! function like any other.
+//!
+//! See Thorup, *High Speed Hashing for Integers and Strings* (2015), for
+//! the first three, and https://github.com/orlp/polymur-hash for Polymur.
+
+use crate::mixers::SplitMix64;
+
+/// The seed used by the `Default` instances in this module.
+pub const DEFAULT_SEED: u64 = 0;
+
+// ====================================
+// Multiply-shift
+
+/// Multiply-shift: `h(x) = (a * x mod 2^64) >> (64 - bits)`, `a` odd.
+///
+/// For `x != y` and `a` chosen uniformly among odd 64-bit integers,
+/// `Pr[h(x) == h(y)] <= 2 / 2^bits`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Hash)]
+pub struct MultiplyShift {
+    pub a: u64,
+}
+
+impl MultiplyShift {
+    /// Use the multiplier `a`, which is forced to be odd.
+    #[inline(always)]
+    pub const fn new(a: u64) -> MultiplyShift {
+        MultiplyShift { a: a | 1 }
+    }
+
+    #[inline(always)]
+    pub const fn from_seed(seed: u64) -> MultiplyShift {
+        MultiplyShift::new(SplitMix64::new(seed).next())
+    }
+
+    /// Hash `x` to `bits` bits, `1 <= bits <= 64`.
+    #[inline(always)]
+    pub const fn hash(&self, x: u64, bits: u32) -> u64 {
+        debug_assert!(bits >= 1 && bits <= 64);
+        self.a.wrapping_mul(x) >> (64 - bits)
+    }
+}
+
+// ====================================
+// Multiply-add-shift
+
+/// Multiply-add-shift: `h(x) = ((a * x + b) mod 2^128) >> (128 - bits)`.
+///
+/// For 64-bit keys and `a`, `b` uniform 128-bit integers, this is
+/// strongly universal for any `bits <= 64`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Hash)]
+pub struct MultiplyAddShift {
+    pub a: u128,
+    pub b: u128,
+}
+
+impl MultiplyAddShift {
+    #[inline(always)]
+    pub const fn new(a: u128, b: u128) -> MultiplyAddShift {
+        MultiplyAddShift { a, b }
+    }
+
+    #[inline(always)]
+    pub const fn from_seed(seed: u64) -> MultiplyAddShift {
+        let mut rng = SplitMix64::new(seed);
+        let a = ((rng.next() as u128) << 64) | rng.next() as u128;
+        let b = ((rng.next() as u128) << 64) | rng.next() as u128;
+        MultiplyAddShift::new(a, b)
+    }
+
+    /// Hash `x` to `bits` bits, `1 <= bits <= 64`.
+    #[inline(always)]
+    pub const fn hash(&self, x: u64, bits: u32) -> u64 {
+        debug_assert!(bits >= 1 && bits <= 64);
+        (self.a.wrapping_mul(x as u128).wrapping_add(self.b) >> (128 - bits)) as u64
+    }
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod multiply_shift_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let ms = MultiplyShift::new(2);
+        assert_eq!(ms.a, 3);
+        assert_eq!(ms.hash(1, 64), 3);
+        assert_eq!(ms.hash(u64::MAX, 1), 1);
+        let mas = MultiplyAddShift::new(1 << 64, 5);
+        assert_eq!(mas.hash(7, 64), 7);
+        assert_eq!(mas.hash(7, 32), 0);
+    }
+
+    #[test]
+    fn universality() {
+        // Average collision rate of two fixed keys over many random
+        // multipliers, against the bound 2 / 2^bits.
+        let (x, y) = (0x1234u64, 0x1234u64 + (1 << 40));
+        let bits = 4;
+        let trials = 20_000;
+        let collisions = (0..trials)
+            .map(MultiplyShift::from_seed)
+            .filter(|h| h.hash(x, bits) == h.hash(y, bits))
+            .count();
+        assert!((collisions as f64) / (trials as f64) <= 2.0 / 16.0 * 1.1);
+        let collisions = (0..trials)
+            .map(MultiplyAddShift::from_seed)
+            .filter(|h| h.hash(x, bits) == h.hash(y, bits))
+            .count();
+        assert!((collisions as f64) / (trials as f64) <= 1.0 / 16.0 * 1.1);
+    }
+}
+
+// ====================================
+// Polynomial hashing mod 2^61 - 1
+
+/// The Mersenne prime 2^61 - 1.
+pub const P61: u64 = (1 << 61) - 1;
+
+/// Reduce `x < 2^122` modulo 2^61 - 1.
+#[inline(always)]
+pub(crate) const fn red61(x: u128) -> u64 {
+    let r = (x as u64 & P61) + (x >> 61) as u64;
+    let r = (r & P61) + (r >> 61);
+    if r >= P61 { r - P61 } else { r }
+}
+
+/// `(a * b + c) mod 2^61 - 1`, for `a, b, c < 2^61`.
+#[inline(always)]
+pub(crate) const fn mul_add61(a: u64, b: u64, c: u64) -> u64 {
+    red61(a as u128 * b as u128 + c as u128)
+}
+
+/// Polynomial hashing modulo 2^61 - 1.
+///
+/// The input is split into 7-byte little-endian words `m_1 .. m_L`, the
+/// last zero-padded, and hashed as
+///
+/// ```text
+/// h = m_1 k^L + m_2 k^(L-1) + ... + m_L k + n   (mod 2^61 - 1)
+/// ```
+///
+/// where `n` is the length in bytes. Two distinct strings of at most `n`
+/// bytes give distinct polynomials of degree at most `ceil(n / 7) + 1`,
+/// which agree on at most that many keys, so for a uniform key in
+/// `0..2^61 - 1` they collide with probability at most
+/// `(ceil(n / 7) + 1) / (2^61 - 1)`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+pub struct Poly61 {
+    pub key: u64,
+    // hash of the complete words so far
+    pub hash: u64,
+    // bytes of an incomplete word, little-endian
+    pub tail: u64,
+    // total length of the input so far
+    pub length: u64,
+}
+
+impl Poly61 {
+    #[inline(always)]
+    pub const fn default() -> Poly61 {
+        Poly61::from_seed(DEFAULT_SEED)
+    }
+
+    /// Use the evaluation point `key`, reduced modulo 2^61 - 1.
+    #[inline(always)]
+    pub const fn new(key: u64) -> Poly61 {
+        Poly61 {
+            key: key % P61,
+            hash: 0,
+            tail: 0,
+            length: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn from_seed(seed: u64) -> Poly61 {
+        Poly61::new(SplitMix64::new(seed).next())
+    }
+
+    /// The same as `from_seed`: the key is the first output of SplitMix64
+    /// seeded with `seed`.
+    #[inline(always)]
+    pub const fn with_seed(seed: u64) -> Poly61 {
+        Poly61::from_seed(seed)
+    }
+
+    #[inline(always)]
+    pub const fn finish(&self) -> u64 {
+        let mut h = self.hash;
+        if !self.length.is_multiple_of(7) {
+            h = mul_add61(h, self.key, self.tail);
+        }
+        mul_add61(h, self.key, self.length % P61)
+    }
+
+    #[inline]
+    pub const fn write(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            let used = (self.length % 7) as u32;
+            self.tail |= (bytes[i] as u64) << (8 * used);
+            self.length += 1;
+            if used == 6 {
+                self.hash = mul_add61(self.hash, self.key, self.tail);
+                self.tail = 0;
+            }
+            i += 1;
+        }
+    }
+}
+
+duplicate_const_traits!(Poly61);
+seedable_hasher!(Poly61);
+hasher_to_fcn!(
+    /// Provide access to Poly61 in a single call, with the default key.
+    poly61,
+    Poly61
+);
+
+// ------------------------------------
+
+#[cfg(test)]
+mod poly61_tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_eq!(poly61(b""), 0);
+        let k = Poly61::default().key;
+        assert!(k < P61);
+        // One partial word, then the length.
+        assert_eq!(poly61(b"a"), mul_add61(mul_add61(0, k, 97), k, 1));
+        assert_ne!(poly61(b"a"), poly61(b"a\0"));
+        assert_ne!(poly61(b"1234567"), poly61(b"12345678"));
+    }
+
+    #[test]
+    fn keyed() {
+        let mut h = Poly61::new(2);
+        h.write(&[1, 0, 0, 0, 0, 0, 0, 3]);
+        // 1 * 2^2 + 3 * 2 + 8
+        assert_eq!(h.finish(), 4 + 6 + 8);
+        assert_eq!(Poly61::new(P61 + 5).key, 5);
+    }
+
+    #[test]
+    fn streaming() {
+        let data = b"abcdefghijklmnopqrstuvwxyz";
+        let mut h = Poly61::default();
+        h.write(&data[..5]);
+        h.write(&data[5..]);
+        assert_eq!(h.finish(), poly61(data));
+    }
+
+    #[test]
+    fn reduction() {
+        assert_eq!(red61(P61 as u128), 0);
+        assert_eq!(red61((P61 as u128) * (P61 as u128)), 0);
+        assert_eq!(red61((P61 as u128 - 1) * (P61 as u128 - 1)), 1);
+    }
+}
+
+// ====================================
+// Polymur-hash
+
+const POLYMUR_ARBITRARY1: u64 = 0x6a09e667f3bcc908;
+const POLYMUR_ARBITRARY2: u64 = 0xbb67ae8584caa73b;
+const POLYMUR_ARBITRARY3: u64 = 0x3c6ef372fe94f82b;
+const POLYMUR_ARBITRARY4: u64 = 0xa54ff53a5f1d36f1;
+
+/// Partial reduction modulo 2^61 - 1: the result is congruent, `< 2^64`.
+#[inline(always)]
+const fn polymur_red611(x: u128) -> u64 {
+    (x as u64 & P61) + (x >> 61) as u64
+}
+
+#[inline(always)]
+const fn polymur_extrared611(x: u64) -> u64 {
+    (x & P61) + (x >> 61)
+}
+
+#[inline(always)]
+const fn polymur_mul128(a: u64, b: u64) -> u128 {
+    a as u128 * b as u128
+}
+
+/// The output mix applied to the polynomial hash.
+#[inline(always)]
+const fn polymur_mix(mut x: u64) -> u64 {
+    x ^= x >> 32;
+    x = x.wrapping_mul(0xe9846af9b1a615d);
+    x ^= x >> 32;
+    x = x.wrapping_mul(0xe9846af9b1a615d);
+    x ^= x >> 28;
+    x
+}
+
+/// `base^e mod 2^61 - 1`.
+pub(crate) const fn pow61(base: u64, mut e: u64) -> u64 {
+    let mut result = 1;
+    let mut b = base;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = red61(polymur_mul128(result, b));
+        }
+        b = red61(polymur_mul128(b, b));
+        e >>= 1;
+    }
+    result
+}
+
+/// Load 0 to 8 bytes, little-endian, without reading out of bounds.
+#[inline(always)]
+const fn polymur_load_le_u64_0_8(buf: &[u8]) -> u64 {
+    let len = buf.len();
+    if len < 4 {
+        if len == 0 {
+            return 0;
+        }
+        let mut v = buf[0] as u64;
+        v |= (buf[len / 2] as u64) << (8 * (len / 2));
+        v |= (buf[len - 1] as u64) << (8 * (len - 1));
+        return v;
+    }
+    let lo = load_int_le!(buf, 0, u32) as u64;
+    let hi = load_int_le!(buf, len - 4, u32) as u64;
+    lo | (hi << (8 * (len - 4)))
+}
+
+/// Load 7 bytes at `i`, little-endian (actually 8, masking off the top).
+#[inline(always)]
+const fn polymur_load_u56(buf: &[u8], i: usize) -> u64 {
+    load_int_le!(buf, i, u64) & 0x00ffffffffffffff
+}
+
+/// Polymur-hash 2.0, after Orson Peters' reference `polymur-hash.h`.
+///
+/// Polymur evaluates a polynomial over 7-byte chunks at a secret point
+/// `k`, like [`Poly61`], but structures the computation so that seven
+/// chunks cost four 64x64 multiplications. The key is `k` together with
+/// its powers `k^2` and `k^7`, and a 64-bit value `s` added after the
+/// output mix. `k` is chosen to generate the multiplicative group modulo
+/// 2^61 - 1, with `k^7` small enough for lazy reduction, giving about 57
+/// bits of key.
+///
+/// For distinct strings of at most `n` bytes, the polynomial hashes
+/// collide with probability at most `n * 2^-60.2`; the output mix is a
+/// bijection and does not change that.
+///
+/// This is a one-shot function, not a Hasher: the chunking depends on
+/// the total length.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord, Hash)]
+pub struct Polymur {
+    pub k: u64,
+    pub k2: u64,
+    pub k7: u64,
+    pub s: u64,
+}
+
+impl Polymur {
+    /// Derive the key from two independent 64-bit random values, one for
+    /// `k` and one for `s`.
+    pub const fn new(mut k_seed: u64, s_seed: u64) -> Polymur {
+        // People love to pass zero.
+        let s = s_seed ^ POLYMUR_ARBITRARY1;
+        loop {
+            // Choose a random exponent coprime to 2^61 - 2. ~35.3% success
+            // rate.
+            k_seed = k_seed.wrapping_add(POLYMUR_ARBITRARY2);
+            let e = (k_seed >> 3) | 1;
+            if e.is_multiple_of(3) || e.is_multiple_of(5) || e.is_multiple_of(7) {
+                continue;
+            }
+            if e.is_multiple_of(11) || e.is_multiple_of(13) || e.is_multiple_of(31) {
+                continue;
+            }
+            if e.is_multiple_of(41)
+                || e.is_multiple_of(61)
+                || e.is_multiple_of(151)
+                || e.is_multiple_of(331)
+                || e.is_multiple_of(1321)
+            {
+                continue;
+            }
+
+            // 37 generates the group, and e is coprime with its order, so
+            // k = 37^e is also a generator.
+            let k = polymur_extrared611(pow61(37, e));
+            let k2 = polymur_extrared611(polymur_red611(polymur_mul128(k, k)));
+            let k3 = polymur_red611(polymur_mul128(k, k2));
+            let k4 = polymur_red611(polymur_mul128(k2, k2));
+            let k7 = polymur_extrared611(polymur_red611(polymur_mul128(k3, k4)));
+            // ~46.3% success rate. The bound on k^7 is needed for lazy
+            // reduction.
+            if k7 < (1 << 60) - (1 << 56) {
+                return Polymur { k, k2, k7, s };
+            }
+        }
+    }
+
+    /// Derive both halves of the key from a single seed.
+    pub const fn from_seed(seed: u64) -> Polymur {
+        Polymur::new(
+            polymur_mix(seed.wrapping_add(POLYMUR_ARBITRARY3)),
+            polymur_mix(seed.wrapping_add(POLYMUR_ARBITRARY4)),
+        )
+    }
+
+    /// The almost-universal polynomial part, before the output mix.
+    const fn poly611(&self, bytes: &[u8], tweak: u64) -> u64 {
+        let mut buf = bytes;
+        let mut len = buf.len();
+        let mut poly_acc = tweak;
+
+        if len <= 7 {
+            let m0 = polymur_load_le_u64_0_8(buf);
+            return poly_acc.wrapping_add(polymur_red611(polymur_mul128(
+                self.k.wrapping_add(m0),
+                self.k2.wrapping_add(len as u64),
+            )));
+        }
+
+        let mut k3 = polymur_red611(polymur_mul128(self.k, self.k2));
+        let mut k4 = polymur_red611(polymur_mul128(self.k2, self.k2));
+        if len >= 50 {
+            let k5 = polymur_extrared611(polymur_red611(polymur_mul128(self.k, k4)));
+            let k6 = polymur_extrared611(polymur_red611(polymur_mul128(self.k2, k4)));
+            k3 = polymur_extrared611(k3);
+            k4 = polymur_extrared611(k4);
+            let mut h: u64 = 0;
+            while len >= 50 {
+                let t0 = polymur_mul128(
+                    self.k.wrapping_add(polymur_load_u56(buf, 0)),
+                    k6.wrapping_add(polymur_load_u56(buf, 7)),
+                );
+                let t1 = polymur_mul128(
+                    self.k2.wrapping_add(polymur_load_u56(buf, 14)),
+                    k5.wrapping_add(polymur_load_u56(buf, 21)),
+                );
+                let t2 = polymur_mul128(
+                    k3.wrapping_add(polymur_load_u56(buf, 28)),
+                    k4.wrapping_add(polymur_load_u56(buf, 35)),
+                );
+                let t3 = polymur_mul128(h.wrapping_add(polymur_load_u56(buf, 42)), self.k7);
+                let s = t0.wrapping_add(t1).wrapping_add(t2).wrapping_add(t3);
+                h = polymur_red611(s);
+                len -= 49;
+                buf = buf.split_at(49).1;
+            }
+            let k14 = polymur_red611(polymur_mul128(self.k7, self.k7));
+            let hk14 = polymur_red611(polymur_mul128(polymur_extrared611(h), k14));
+            poly_acc = poly_acc.wrapping_add(polymur_extrared611(hk14));
+        }
+
+        if len >= 8 {
+            let m0 = polymur_load_u56(buf, 0);
+            let m1 = polymur_load_u56(buf, (len - 7) / 2);
+            let m2 = load_int_le!(buf, len - 8, u64) >> 8;
+            let t0 = polymur_mul128(self.k2.wrapping_add(m0), self.k7.wrapping_add(m1));
+            let t1 = polymur_mul128(self.k.wrapping_add(m2), k3.wrapping_add(len as u64));
+            if len <= 21 {
+                return poly_acc.wrapping_add(polymur_red611(t0.wrapping_add(t1)));
+            }
+            let m3 = polymur_load_u56(buf, 7);
+            let m4 = polymur_load_u56(buf, 14);
+            let m5 = polymur_load_u56(buf, len - 21);
+            let m6 = polymur_load_u56(buf, len - 14);
+            let t0r = polymur_red611(t0);
+            let t2 = polymur_mul128(self.k2.wrapping_add(m3), self.k7.wrapping_add(m4));
+            let t3 = polymur_mul128(t0r.wrapping_add(m5), k4.wrapping_add(m6));
+            let s = t1.wrapping_add(t2).wrapping_add(t3);
+            return poly_acc.wrapping_add(polymur_red611(s));
+        }
+
+        let m0 = polymur_load_le_u64_0_8(buf);
+        poly_acc.wrapping_add(polymur_red611(polymur_mul128(
+            self.k.wrapping_add(m0),
+            self.k2.wrapping_add(len as u64),
+        )))
+    }
+
+    /// Hash `bytes` with a tweak, which is added to the polynomial hash
+    /// before mixing. Distinct tweaks give unrelated hashes of the same
+    /// input, for the price of one key.
+    #[inline]
+    pub const fn hash_with_tweak(&self, bytes: &[u8], tweak: u64) -> u64 {
+        polymur_mix(self.poly611(bytes, tweak)).wrapping_add(self.s)
+    }
+
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u64 {
+        self.hash_with_tweak(bytes, 0)
+    }
+}
+
+/// Polymur-hash with the key derived from [`DEFAULT_SEED`].
+pub const fn polymur(bytes: &[u8]) -> u64 {
+    const POLYMUR: Polymur = Polymur::from_seed(DEFAULT_SEED);
+    POLYMUR.hash(bytes)
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod polymur_tests {
+    use super::*;
+
+    #[test]
+    fn key() {
+        let p = Polymur::from_seed(DEFAULT_SEED);
+        assert!(p.k7 < (1 << 60) - (1 << 56));
+        assert_eq!(red61(polymur_mul128(p.k, p.k)), p.k2 % P61);
+        assert_eq!(pow61(p.k % P61, 7), p.k7 % P61);
+        assert_ne!(Polymur::from_seed(1), p);
+    }
+
+    #[test]
+    fn lengths() {
+        // Every code path, with no collisions between prefixes of one
+        // buffer.
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 7 + 3) as u8).collect();
+        let mut hashes: Vec<u64> = (0..data.len()).map(|n| polymur(&data[..n])).collect();
+        hashes.sort();
+        hashes.dedup();
+        assert_eq!(hashes.len(), data.len());
+    }
+
+    #[test]
+    fn keyed() {
+        let a = Polymur::from_seed(1);
+        let b = Polymur::from_seed(2);
+        assert_ne!(a.hash(b"abcdefg"), b.hash(b"abcdefg"));
+        assert_ne!(a.hash(b"abcdefg"), a.hash_with_tweak(b"abcdefg", 1));
+    }
+
+    #[test]
+    fn const_eval() {
+        const H: u64 = polymur(b"abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(H, polymur(b"abcdefghijklmnopqrstuvwxyz"));
+    }
+}
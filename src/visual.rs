@@ -0,0 +1,219 @@
+//! Pictures of hash functions.
+//!
+//! > Several hash algorithms are presented, with a hash visualiser to
+//! > help you evaluate them. -- http://www.cse.yorku.ca/~oz/hash.html
+//!
+//! - [`avalanche_image`] draws an [`AvalancheMatrix`]: one row per input
+//!   bit and one column per output bit, white where the output bit flips
+//!   half the time, shading to blue where it flips less often and red
+//!   where it flips more.
+//! - [`bitmap_image`] draws hashes of sequential keys, from
+//!   [`sequential_hashes`]: one row per key and one column per bit,
+//!   black for 1 and white for 0. A good hash looks like noise; a weak
+//!   one has stripes.
+//!
+//! Images are written as binary PPM or PGM, which most image viewers
+//! read, so there is no dependency on an image library. The same data is
+//! available as CSV.
+
+use core::fmt::Write;
+
+use crate::battery::AvalancheMatrix;
+
+/// An RGB image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    /// The pixels, row by row from the top.
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    /// The image with each pixel drawn as a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The image as a binary PPM (P6) file.
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut file = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        file.extend(self.pixels.iter().flatten());
+        file
+    }
+
+    /// The image in shades of gray, as a binary PGM (P5) file.
+    pub fn pgm(&self) -> Vec<u8> {
+        let mut file = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        file.extend(
+            self.pixels
+                .iter()
+                .map(|&[r, g, b]| ((r as u16 + g as u16 + b as u16) / 3) as u8),
+        );
+        file
+    }
+}
+
+// ====================================
+// Avalanche
+
+/// The colour of a flip probability: white for 1/2, blue for 0 and red
+/// for 1.
+fn diverging(p: f64) -> [u8; 3] {
+    let d = (2.0 * p - 1.0).clamp(-1.0, 1.0);
+    let fade = (255.0 * (1.0 - d.abs())).round() as u8;
+    if d < 0.0 {
+        [fade, fade, 255]
+    } else {
+        [255, fade, fade]
+    }
+}
+
+/// `matrix` as an image, `bits` pixels wide and one row per input bit.
+pub fn avalanche_image(matrix: &AvalancheMatrix) -> Image {
+    let width = matrix.bits as usize;
+    let height = matrix.input_bits();
+    let pixels = (0..height)
+        .flat_map(|i| (0..width).map(move |j| diverging(matrix.probability(i, j))))
+        .collect();
+    Image {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// `matrix` as CSV: a header row of output bit numbers, then one row per
+/// input bit, of the input bit number and the flip probability of each
+/// output bit.
+pub fn avalanche_csv(matrix: &AvalancheMatrix) -> String {
+    let mut csv = String::from("input");
+    for j in 0..matrix.bits {
+        write!(csv, ",{}", j).unwrap();
+    }
+    csv.push('\n');
+    for i in 0..matrix.input_bits() {
+        write!(csv, "{}", i).unwrap();
+        for j in 0..matrix.bits as usize {
+            write!(csv, ",{:.4}", matrix.probability(i, j)).unwrap();
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+// ====================================
+// Bitmaps
+
+/// The hashes of the keys 0 to `n` - 1, each as 8 little-endian bytes.
+pub fn sequential_hashes<F: Fn(&[u8]) -> u64>(hash: F, n: u64) -> Vec<u64> {
+    (0..n).map(|i| hash(&i.to_le_bytes())).collect()
+}
+
+/// The low `bits` bits of `hashes`, one row per hash, with bit 0 on the
+/// left.
+pub fn bitmap_image(hashes: &[u64], bits: u32) -> Image {
+    let pixels = hashes
+        .iter()
+        .flat_map(|&h| (0..bits).map(move |j| if (h >> j) & 1 == 1 { [0; 3] } else { [255; 3] }))
+        .collect();
+    Image {
+        width: bits as usize,
+        height: hashes.len(),
+        pixels,
+    }
+}
+
+/// `hashes` as CSV: a header row, then one row per hash, of the key
+/// index, the hash in hex and its low `bits` bits.
+pub fn bitmap_csv(hashes: &[u64], bits: u32) -> String {
+    let mut csv = String::from("key,hash");
+    for j in 0..bits {
+        write!(csv, ",{}", j).unwrap();
+    }
+    csv.push('\n');
+    for (i, &h) in hashes.iter().enumerate() {
+        write!(csv, "{},{:016x}", i, h).unwrap();
+        for j in 0..bits {
+            write!(csv, ",{}", (h >> j) & 1).unwrap();
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+// ------------------------------------
+
+#[cfg(test)]
+mod visual_tests {
+    use super::*;
+
+    #[test]
+    fn images() {
+        let image = bitmap_image(&[0b01, 0b10], 2);
+        assert_eq!(image.pixels, [[0; 3], [255; 3], [255; 3], [0; 3]]);
+        assert_eq!(
+            image.ppm(),
+            b"P6\n2 2\n255\n\0\0\0\xff\xff\xff\xff\xff\xff\0\0\0"
+        );
+        assert_eq!(image.pgm(), b"P5\n2 2\n255\n\0\xff\xff\0");
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width, scaled.height), (4, 4));
+        assert_eq!(scaled.pixels[0..4], [[0; 3], [0; 3], [255; 3], [255; 3]]);
+        assert_eq!(scaled.pixels[4..8], scaled.pixels[0..4]);
+        assert_eq!(
+            bitmap_csv(&[0b01], 2),
+            "key,hash,0,1\n0,0000000000000001,1,0\n"
+        );
+        assert_eq!(diverging(0.5), [255, 255, 255]);
+        assert_eq!(diverging(0.0), [0, 0, 255]);
+        assert_eq!(diverging(1.0), [255, 0, 0]);
+    }
+
+    /// The share of pixels that are far from white.
+    fn dark(image: &Image) -> f64 {
+        let dark = image
+            .pixels
+            .iter()
+            .filter(|p| p.iter().any(|&c| c < 128))
+            .count();
+        dark as f64 / image.pixels.len() as f64
+    }
+
+    #[cfg(all(feature = "oz", feature = "jenkins"))]
+    #[test]
+    fn loselose_and_spooky() {
+        use crate::jenkins::spooky_hash::spooky;
+        use crate::oz::loselose;
+
+        let spooky_matrix = AvalancheMatrix::new(spooky, 8, 64, 200, 0);
+        let loselose_matrix = AvalancheMatrix::new(loselose, 8, 64, 200, 0);
+        assert_eq!(dark(&avalanche_image(&spooky_matrix)), 0.0);
+        assert!(dark(&avalanche_image(&loselose_matrix)) > 0.8);
+        let csv = avalanche_csv(&loselose_matrix);
+        assert_eq!(csv.lines().count(), 65);
+        // flipping bit 0 of a byte always flips bit 0 of the sum, and
+        // never bit 63
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("0,1.0000,") && row.ends_with(",0.0000"));
+
+        // LoseLose's sums of 8 bytes are less than 2^11, and the bits of
+        // sequential keys' sums are stripes.
+        let hashes = sequential_hashes(loselose, 256);
+        let image = bitmap_image(&hashes, 64);
+        assert!((11..64).all(|j| (0..256).all(|i| image.pixels[i * 64 + j] == [255; 3])));
+        let hashes = sequential_hashes(spooky, 256);
+        let ones = hashes.iter().map(|h| h.count_ones()).sum::<u32>();
+        assert!((ones as f64 / (256.0 * 64.0) - 0.5).abs() < 0.02);
+    }
+}
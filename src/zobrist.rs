@@ -0,0 +1,177 @@
+//! Zobrist hashing, from Albert Zobrist's *A New Hashing Method with
+//! Application for Game Playing* (1970), and listed in Bob Jenkins' Dr.
+//! Dobb's survey (http://www.burtleburtle.net/bob/hash/doobs.html).
+//!
+//! A state made of pieces on squares hashes to the xor of one random key
+//! per occupied `(piece, square)`. Since xor is its own inverse, moving a
+//! piece updates the hash in two `toggle`s instead of rehashing the whole
+//! state, which is what makes transposition tables in game-tree and
+//! puzzle search cheap. The hash does not depend on the order pieces were
+//! placed in, so transpositions (the same state reached by different
+//! move orders) get the same hash.
+//!
+//! The keys are generated at compile time from a seed with
+//! [`SplitMix64`]. 64-bit keys are the usual choice; with billions of
+//! stored states, 128-bit keys make false matches negligible.
+//!
+//! This is [simple tabulation](crate::tabulation) with the key's bytes
+//! replaced by arbitrary sets of `(piece, square)` pairs.
+
+use crate::mixers::SplitMix64;
+
+/// The seed used by `default()`.
+pub const DEFAULT_SEED: u64 = 0;
+
+macro_rules! zobrist_table {
+    ($(#[$attr:meta])* $name:ident, $key:ty, $next:expr) => {
+        $(#[$attr])*
+        #[derive(PartialEq, Eq, Copy, Clone, Debug, PartialOrd, Ord)]
+        pub struct $name<const PIECES: usize, const SQUARES: usize> {
+            pub keys: [[$key; SQUARES]; PIECES],
+        }
+
+        impl<const PIECES: usize, const SQUARES: usize> $name<PIECES, SQUARES> {
+            #[inline]
+            pub const fn default() -> $name<PIECES, SQUARES> {
+                $name::new(DEFAULT_SEED)
+            }
+
+            /// Generate the keys from `seed`.
+            pub const fn new(seed: u64) -> $name<PIECES, SQUARES> {
+                let mut rng = SplitMix64::new(seed);
+                let mut keys = [[0; SQUARES]; PIECES];
+                let mut p = 0;
+                while p < PIECES {
+                    let mut s = 0;
+                    while s < SQUARES {
+                        keys[p][s] = $next(&mut rng);
+                        s += 1;
+                    }
+                    p += 1;
+                }
+                $name { keys }
+            }
+
+            /// The key for `piece` on `square`.
+            #[inline(always)]
+            pub const fn key(&self, piece: usize, square: usize) -> $key {
+                self.keys[piece][square]
+            }
+
+            /// Add `piece` on `square` to `hash`, or remove it if it is
+            /// already there.
+            #[inline(always)]
+            pub const fn toggle(&self, hash: &mut $key, piece: usize, square: usize) {
+                *hash ^= self.keys[piece][square];
+            }
+
+            /// Move `piece` from one square to another.
+            #[inline(always)]
+            pub const fn move_piece(&self, hash: &mut $key, piece: usize, from: usize, to: usize) {
+                *hash ^= self.keys[piece][from] ^ self.keys[piece][to];
+            }
+
+            /// Hash a whole state, given as `(piece, square)` pairs.
+            pub const fn hash(&self, pieces: &[(usize, usize)]) -> $key {
+                let mut hash = 0;
+                let mut i = 0;
+                while i < pieces.len() {
+                    self.toggle(&mut hash, pieces[i].0, pieces[i].1);
+                    i += 1;
+                }
+                hash
+            }
+        }
+
+        impl<const PIECES: usize, const SQUARES: usize> Default for $name<PIECES, SQUARES> {
+            #[inline]
+            fn default() -> $name<PIECES, SQUARES> {
+                $name::new(DEFAULT_SEED)
+            }
+        }
+    };
+}
+
+const fn next64(rng: &mut SplitMix64) -> u64 {
+    rng.next()
+}
+
+const fn next128(rng: &mut SplitMix64) -> u128 {
+    let hi = rng.next() as u128;
+    (hi << 64) | rng.next() as u128
+}
+
+zobrist_table!(
+    /// Zobrist keys for `PIECES` kinds of piece on `SQUARES` squares, with
+    /// 64-bit hashes.
+    Zobrist,
+    u64,
+    next64
+);
+
+zobrist_table!(
+    /// Zobrist keys for `PIECES` kinds of piece on `SQUARES` squares, with
+    /// 128-bit hashes.
+    Zobrist128,
+    u128,
+    next128
+);
+
+// ------------------------------------
+
+#[cfg(test)]
+mod zobrist_tests {
+    use super::*;
+
+    // Twelve kinds of chess piece on 64 squares.
+    static CHESS: Zobrist<12, 64> = Zobrist::default();
+    static CHESS128: Zobrist128<12, 64> = Zobrist128::default();
+
+    #[test]
+    fn basic() {
+        assert_eq!(CHESS.hash(&[]), 0);
+        assert_eq!(CHESS.hash(&[(3, 10)]), CHESS.key(3, 10));
+        assert_ne!(CHESS.key(3, 10), CHESS.key(10, 3));
+        assert_ne!(Zobrist::<12, 64>::new(1), CHESS);
+        assert_eq!(CHESS128.hash(&[(0, 0)]), CHESS128.key(0, 0));
+    }
+
+    #[test]
+    fn incremental() {
+        let start = [(0, 0), (1, 1), (2, 2)];
+        let mut h = CHESS.hash(&start);
+        // Order does not matter.
+        assert_eq!(h, CHESS.hash(&[(2, 2), (0, 0), (1, 1)]));
+        // Toggling twice is a no-op.
+        CHESS.toggle(&mut h, 5, 5);
+        assert_ne!(h, CHESS.hash(&start));
+        CHESS.toggle(&mut h, 5, 5);
+        assert_eq!(h, CHESS.hash(&start));
+        // A move matches hashing the new state from scratch.
+        CHESS.move_piece(&mut h, 1, 1, 9);
+        assert_eq!(h, CHESS.hash(&[(0, 0), (1, 9), (2, 2)]));
+
+        let mut h = CHESS128.hash(&start);
+        CHESS128.move_piece(&mut h, 1, 1, 9);
+        assert_eq!(h, CHESS128.hash(&[(0, 0), (1, 9), (2, 2)]));
+    }
+
+    #[test]
+    fn transpositions() {
+        // Two move orders reaching the same state.
+        let mut a = CHESS.hash(&[(0, 0), (1, 1)]);
+        let mut b = a;
+        CHESS.move_piece(&mut a, 0, 0, 8);
+        CHESS.move_piece(&mut a, 1, 1, 9);
+        CHESS.move_piece(&mut b, 1, 1, 9);
+        CHESS.move_piece(&mut b, 0, 0, 8);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn const_eval() {
+        const T: Zobrist<2, 9> = Zobrist::new(7);
+        const H: u64 = T.hash(&[(0, 4), (1, 0)]);
+        assert_eq!(H, T.key(0, 4) ^ T.key(1, 0));
+    }
+}